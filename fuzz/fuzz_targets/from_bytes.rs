@@ -0,0 +1,36 @@
+//! Feeds arbitrary bytes into the deserializing and raw constructors of `UnthBuf`.
+//! 
+//! Run with `cargo fuzz run from_bytes`; every input must either be rejected with an error,
+//! or produce a buffer whose every element can be read without panicking.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use unthbuf::{aligned::AlignedLayout, packed::PackedLayout, Bits, CellLayout, UnthBuf};
+
+fn check<CL: CellLayout>(data: &[u8]) {
+    if let Ok(buf) = UnthBuf::<CL>::from_be_bytes(data) {
+        for index in 0..buf.len() {
+            assert!(buf.get(index).is_some());
+        }
+    }
+    
+    // Interpret the leading bytes as bit-size and big-endian 64-bit capacity, and the rest as cells.
+    // Neither is clamped, so oversized bit-sizes and overflowing capacities reach the constructor.
+    let [bits, c0, c1, c2, c3, c4, c5, c6, c7, cells @ ..] = data else {return};
+    let Some(bits) = Bits::new(*bits) else {return};
+    let capacity = u64::from_be_bytes([*c0, *c1, *c2, *c3, *c4, *c5, *c6, *c7]) as usize;
+    let cells: Box<[usize]> = cells.chunks(size_of::<usize>())
+        .map(|chunk| chunk.iter().fold(0, |acc, &byte| acc << 8 | byte as usize))
+        .collect();
+    
+    if let Ok(buf) = UnthBuf::<CL>::from_raw_parts(bits, capacity, cells) {
+        for index in 0..buf.len() {
+            assert!(buf.get(index).is_some());
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    check::<AlignedLayout>(data);
+    check::<PackedLayout>(data);
+});
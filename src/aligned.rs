@@ -1,5 +1,5 @@
 //! Layout that stores integers in groups *within* word boundaries.
-use crate::{UnthBuf, CellLayout, Bits, BITS_PER_CELL};
+use crate::{UnthBuf, UnthBufRef, UnthBufMut, CellLayout, ElementLocation, Bits, BITS_PER_CELL};
 
 /// Layout that stores integers in groups *within* word boundaries.
 #[derive(Clone, Copy)]
@@ -8,10 +8,12 @@ pub struct AlignedLayout;
 impl CellLayout for AlignedLayout {
     type Location = AlignedLocation;
     
+    const ALIGNED: bool = true;
+    
     #[inline(always)]
     fn get_cell_count(capacity: usize, bits: Bits) -> usize {
         let elements_per_cell = get_aligned_elements_per_cell(bits.get());
-        (capacity + elements_per_cell as usize) / elements_per_cell as usize
+        capacity / elements_per_cell as usize + 1
     }
     
     fn get_exact_bit_count(buf: &UnthBuf<Self>) -> usize {
@@ -21,8 +23,67 @@ impl CellLayout for AlignedLayout {
         * buf.bits.get() as usize
     }
     
+    fn get_cell_mask(buf: &UnthBufRef<'_, Self>, cell: usize) -> usize {
+        let first = cell.saturating_mul(buf.elpc as usize);
+        let elements = buf.capacity.saturating_sub(first).min(buf.elpc as usize);
+        UnthBuf::<Self>::mask_from_bits(elements as u8 * buf.bits.get())
+    }
+    
+    fn decode_into(buf: &UnthBufRef<'_, Self>, out: &mut [usize]) -> usize {
+        let count = out.len().min(buf.capacity);
+        let bits = buf.bits.get() as u32;
+        
+        // Every cell holds `elpc` elements, so whole cells are unpacked at once, without locating each element.
+        for (chunk, &cell) in out[..count].chunks_mut(buf.elpc as usize).zip(buf.data.iter()) {
+            let mut cell = cell;
+            for slot in chunk {
+                *slot = cell & buf.mask;
+                cell = cell.overflowing_shr(bits).0;
+            }
+        }
+        count
+    }
+    
+    fn fill(buf: &mut UnthBufMut<'_, Self>, value: usize) {
+        // Every cell holds the same elements, so the value is repeated once per element of a cell.
+        let pattern = (0..buf.elpc).fold(0, |pattern, element| pattern | value << (element * buf.bits.get()));
+        
+        for cell in 0..buf.data.len() {
+            let mask = Self::get_cell_mask(&buf.view(), cell);
+            buf.data[cell] = pattern & mask;
+        }
+    }
+    
+    fn fill_pattern(buf: &mut UnthBufMut<'_, Self>, pattern: &[usize]) {
+        if !(buf.elpc as usize).is_multiple_of(pattern.len()) {
+            buf.fill_from(pattern.iter().copied().cycle());
+            return
+        }
+        
+        // Every cell starts at the beginning of the pattern, so all cells hold the same elements.
+        let bits = buf.bits.get() as usize;
+        let tile = pattern.iter()
+            .cycle()
+            .take(buf.elpc as usize)
+            .enumerate()
+            .fold(0, |tile, (element, &value)| tile | value << (element * bits));
+        
+        for cell in 0..buf.data.len() {
+            let mask = Self::get_cell_mask(&buf.view(), cell);
+            buf.data[cell] = tile & mask;
+        }
+    }
+    
+    fn apply_mask(buf: &mut UnthBufMut<'_, Self>, mask: usize) {
+        // Every cell holds the same elements, so the mask is repeated once per element of a cell.
+        let bits = buf.bits.get() as usize;
+        let pattern = (0..buf.elpc as usize).fold(0, |pattern, element| pattern | mask << (element * bits));
+        
+        buf.data.iter_mut().for_each(|cell| *cell &= pattern);
+    }
+    
     #[inline(always)]
-    fn location_of(buf: &UnthBuf<Self>, index: usize) -> Self::Location {
+    fn location_of(buf: &UnthBufRef<'_, Self>, index: usize) -> Self::Location {
         //if !self.is_index(index) {eprintln!("index {index} is out of bounds; {:?}", self)}
         let cell = index / (buf.elpc as usize);
         //if !self.is_cell(cell) {eprintln!("index-{index} / cell-{cell} (E={elements_per_cell}) is outside the bounds of {:?}", self)}
@@ -34,8 +95,8 @@ impl CellLayout for AlignedLayout {
     }
 
     #[inline(always)]
-    unsafe fn set_unchecked(buf: &mut UnthBuf<Self>, index: usize, value: usize) {
-        let loc = Self::location_of(buf, index);
+    unsafe fn set_unchecked(buf: &mut UnthBufMut<'_, Self>, index: usize, value: usize) {
+        let loc = Self::location_of(&buf.view(), index);
         
         let mut cell = *buf.data.get_unchecked(loc.cell);
         
@@ -46,7 +107,7 @@ impl CellLayout for AlignedLayout {
     }
 
     #[inline(always)]
-    unsafe fn get_unchecked(buf: &UnthBuf<Self>, index: usize) -> usize {
+    unsafe fn get_unchecked(buf: &UnthBufRef<'_, Self>, index: usize) -> usize {
         let loc = Self::location_of(buf, index);
         
         //if !self.is_cell(loc.cell) {panic!("aligned cell @{index} -> {:?} out of bounds; {:?}", loc, self)}
@@ -63,6 +124,13 @@ pub struct AlignedLocation {
     pub(crate) mask: usize,
 }
 
+impl ElementLocation for AlignedLocation {
+    #[inline(always)]
+    fn cell(&self) -> usize {
+        self.cell
+    }
+}
+
 impl core::fmt::Debug for AlignedLocation {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "[#{} <<{} &{:b}]", self.cell, self.offset, self.mask)
@@ -0,0 +1,35 @@
+//! Element-wise arithmetic on [`UnthBuf`]
+use crate::{UnthBuf, CellLayout};
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Replaces every element `v` with `v & mask`.
+    /// 
+    /// The [`crate::aligned::AlignedLayout`] does this cell-wise, by repeating the mask for every element of a cell.
+    pub fn apply_mask(&mut self, mask: usize) {
+        let mask = mask & self.mask;
+        CL::apply_mask(&mut self.view_mut(), mask);
+    }
+    
+    /// Adds `n` to every element, clamping the result to the largest value that fits.
+    pub fn saturating_add_scalar(&mut self, n: usize) {
+        let max = self.mask;
+        self.map_in_place(|element| element.saturating_add(n).min(max));
+    }
+    
+    /// Adds `n` to every element, wrapping the result around at the bit-size.
+    pub fn wrapping_add_scalar(&mut self, n: usize) {
+        let mask = self.mask;
+        self.map_in_place(|element| element.wrapping_add(n) & mask);
+    }
+    
+    /// Replaces every element with the result of the given function, which must fit.
+    fn map_in_place(&mut self, mut f: impl FnMut(usize) -> usize) {
+        for index in self.get_indices() {
+            // This is safe, as the index comes from the range of valid indices.
+            unsafe {
+                let element = self.get_unchecked(index);
+                self.set_unchecked(index, f(element));
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Thread-safe [`UnthBuf`] with atomic cells.
+use crate::{UnthBuf, CellLayout, Bits, aligned::AlignedLayout};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A buffer of `bits`-sized unsigned integer elements, whose cells can be updated concurrently.
+/// 
+/// Elements are updated via compare-and-swap loops on the cell containing them.
+/// 
+/// Only the [`AlignedLayout`] is supported, since it guarantees that every element lives within a single cell;
+/// an element of the packed layout may straddle two cells, which cannot be updated atomically.
+/// Thus, `AtomicUnthBuf<PackedLayout>` has no methods, and cannot be constructed.
+pub struct AtomicUnthBuf<CL: CellLayout = AlignedLayout> {
+    /// Capacity of the buffer.
+    pub(crate) capacity: usize,
+    
+    /// Buffer of atomic cells, containing [`Self::bits`]-sized unsigned integer elements.
+    pub(crate) data: Box<[AtomicUsize]>,
+    
+    /// Bit-size of an individual element in [`Self::data`].
+    pub(crate) bits: Bits,
+    
+    /// Mask of bits covering a single element.
+    pub(crate) mask: usize,
+    
+    /// Elements per cell.
+    pub(crate) elpc: u8,
+    
+    /// Marker for cell layout.
+    pub(crate) cell_layout: core::marker::PhantomData<CL>
+}
+
+impl AtomicUnthBuf<AlignedLayout> {
+    /// Creates a new [`AtomicUnthBuf`] with the given `capacity` and `bits`-size, filled with `0`.
+    /// 
+    /// # Panic
+    /// - Panics if the given `capacity` is `0`.
+    pub fn new(bits: Bits, capacity: usize) -> Self {
+        UnthBuf::new(bits, capacity).into()
+    }
+    
+    /// Turns this back into a regular, non-atomic [`UnthBuf`].
+    pub fn into_inner(self) -> UnthBuf<AlignedLayout> {
+        let data: Box<[usize]> = self.data.into_vec().into_iter().map(AtomicUsize::into_inner).collect();
+        UnthBuf::from_raw_parts(self.bits, self.capacity, data)
+            .expect("atomic buffer has the same shape as its origin")
+    }
+    
+    /// Gets how many elements are stored within.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.capacity
+    }
+    
+    /// Always `false`, as a buffer cannot be of 0 capacity.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    
+    /// Checks if the given value can be stored in this buffer.
+    #[inline(always)]
+    pub fn can_element_fit(&self, value: usize) -> bool {
+        (value & self.mask) == value
+    }
+    
+    /// Is the given index (`0..self.len()`) valid for this buffer?
+    #[inline(always)]
+    pub fn is_index(&self, index: usize) -> bool {
+        index < self.capacity
+    }
+    
+    /// Returns the cell, offset and mask of the element at the given `index`.
+    #[inline(always)]
+    fn locate(&self, index: usize) -> (&AtomicUsize, u8, usize) {
+        let offset = (index % self.elpc as usize) as u8 * self.bits.get();
+        (&self.data[index / self.elpc as usize], offset, self.mask << offset)
+    }
+    
+    /// Returns the element at the given `index`.
+    /// 
+    /// Out-of-bounds access will return [`Option::None`].
+    pub fn get(&self, index: usize) -> Option<usize> {
+        if !self.is_index(index) {return None}
+        let (cell, offset, mask) = self.locate(index);
+        Some((cell.load(Ordering::Acquire) & mask) >> offset)
+    }
+    
+    /// Tries to set the element at the given `index` to the provided `value`.
+    /// 
+    /// # Errors
+    /// - If the value does not fit; check with [`Self::can_element_fit`].
+    /// - If the index is out of bounds; check with [`Self::is_index`].
+    pub fn set(&self, index: usize, value: usize) -> Result<(),&'static str> {
+        if !self.can_element_fit(value) {return Err("value does not fit")}
+        if !self.is_index(index) {return Err("index is out-of-bounds")}
+        
+        let (cell, offset, mask) = self.locate(index);
+        let _ = cell.fetch_update(Ordering::AcqRel, Ordering::Acquire, |bits| {
+            Some((bits & !mask) | (value << offset))
+        });
+        Ok(())
+    }
+    
+    /// Sets the element at the given `index` to `new`, if it currently is `current`.
+    /// 
+    /// Returns the previous element; if it is `current`, the swap happened.
+    /// 
+    /// # Panic
+    /// - Panics if the index is out of bounds.
+    /// - Panics if `new` does not fit.
+    pub fn compare_exchange(&self, index: usize, current: usize, new: usize) -> Result<usize, usize> {
+        assert!(self.is_index(index), "index {index} is out of bounds");
+        assert!(self.can_element_fit(new), "given value (0x{new:X}) does not fit into {} bits", self.bits);
+        
+        let (cell, offset, mask) = self.locate(index);
+        let mut bits = cell.load(Ordering::Acquire);
+        loop {
+            let element = (bits & mask) >> offset;
+            if element != current {return Err(element)}
+            
+            let updated = (bits & !mask) | (new << offset);
+            match cell.compare_exchange_weak(bits, updated, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return Ok(element),
+                Err(actual) => bits = actual
+            }
+        }
+    }
+}
+
+impl From<UnthBuf<AlignedLayout>> for AtomicUnthBuf<AlignedLayout> {
+    fn from(buf: UnthBuf<AlignedLayout>) -> Self {
+        Self {
+            capacity: buf.capacity,
+            data: buf.data.into_vec().into_iter().map(AtomicUsize::new).collect(),
+            bits: buf.bits,
+            mask: buf.mask,
+            elpc: buf.elpc,
+            cell_layout: core::marker::PhantomData,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Bit-level access to [`UnthBuf`]
+use crate::{UnthBuf, UnthBufError, CellLayout, Bits, BITS_PER_CELL};
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns the amount of bits occupied by elements; the same as [`Self::get_exact_bit_count`].
+    /// 
+    /// For the packed layout, the bits `0..bit_len()` of the raw bit stream are exactly the elements.
+    #[inline(always)]
+    pub fn bit_len(&self) -> usize {
+        self.get_exact_bit_count()
+    }
+    
+    /// Returns the bit at the given position of the raw bit stream, regardless of element boundaries.
+    /// 
+    /// The bit stream spans all cells, LSB-first; for the aligned layout it thus includes intra-cell padding.
+    /// 
+    /// Out-of-bounds access, beyond [`Self::get_total_bit_count`], will return [`Option::None`].
+    pub fn get_bit(&self, bit_index: usize) -> Option<bool> {
+        let cell = self.data.get(bit_index / BITS_PER_CELL as usize)?;
+        Some((cell >> (bit_index % BITS_PER_CELL as usize)) & 1 == 1)
+    }
+    
+    /// Sets the bit at the given position of the raw bit stream, regardless of element boundaries.
+    /// 
+    /// The bit stream spans all cells, LSB-first; for the aligned layout it thus includes intra-cell padding.
+    /// 
+    /// # Panic
+    /// - Panics if the position is beyond [`Self::get_total_bit_count`].
+    pub fn set_bit(&mut self, bit_index: usize, value: bool) {
+        let cell = &mut self.data[bit_index / BITS_PER_CELL as usize];
+        let bit = 1 << (bit_index % BITS_PER_CELL as usize);
+        
+        if value {
+            *cell |= bit;
+        } else {
+            *cell &= !bit;
+        }
+    }
+    
+    /// Creates a new [`UnthBuf`] with the given `bits`-size from a slice of booleans, storing them as `0` or `1`.
+    /// 
+    /// This is primarily meant for bitsets, i.e. a `bits`-size of `1`.
+    /// 
+    /// # Panic
+    /// - Panics if the given slice is empty.
+    pub fn from_bool_vec(bits: Bits, v: &[bool]) -> Self {
+        Self::new_from_sized_iter(bits, v.iter().map(|&value| value as usize))
+    }
+    
+    /// Returns whether each element is non-zero.
+    /// 
+    /// This is only lossless for bitsets, i.e. a `bits`-size of `1`.
+    pub fn to_bool_vec(&self) -> Vec<bool> {
+        self.get_indices()
+            // This is safe, as the index comes from the range of valid indices.
+            .map(|index| unsafe {self.get_unchecked(index)} != 0)
+            .collect()
+    }
+    
+    /// Returns whether any index is set in both bitsets.
+    /// 
+    /// This is computed cell-wise, ignoring padding.
+    /// 
+    /// # Errors
+    /// - If either `bits`-size is not `1`.
+    /// - If the capacities of both bitsets do not match.
+    pub fn intersects(&self, other: &Self) -> Result<bool, UnthBufError> {
+        self.check_bitset_pair(other)?;
+        Ok(self.zip_cells(other).any(|(lhs, rhs, mask)| lhs & rhs & mask != 0))
+    }
+    
+    /// Returns whether every index set in this bitset is also set in `other`.
+    /// 
+    /// This is computed cell-wise, ignoring padding.
+    /// 
+    /// # Errors
+    /// - If either `bits`-size is not `1`.
+    /// - If the capacities of both bitsets do not match.
+    pub fn is_subset(&self, other: &Self) -> Result<bool, UnthBufError> {
+        self.check_bitset_pair(other)?;
+        Ok(self.zip_cells(other).all(|(lhs, rhs, mask)| lhs & !rhs & mask == 0))
+    }
+    
+    /// Flips the element at the given `index` of a bitset, returning its new value.
+    /// 
+    /// # Errors
+    /// - If the `bits`-size is not `1`.
+    /// - If the index is out of bounds; check with [`Self::is_index`].
+    pub fn toggle(&mut self, index: usize) -> Result<bool, UnthBufError> {
+        self.check_bitset()?;
+        self.check_element(index, 0)?;
+        
+        // This is safe, as the index was checked above, and a single bit always fits.
+        unsafe {
+            let value = self.get_unchecked(index) ^ 1;
+            self.set_unchecked(index, value);
+            Ok(value == 1)
+        }
+    }
+    
+    /// Checks that this buffer is a bitset, i.e. has a `bits`-size of `1`.
+    fn check_bitset(&self) -> Result<(), UnthBufError> {
+        if self.bits != Bits::MIN {return Err(UnthBufError::BitsMismatch { expected: Bits::MIN, actual: self.bits })}
+        Ok(())
+    }
+    
+    /// Checks that both buffers are bitsets of the same capacity.
+    fn check_bitset_pair(&self, other: &Self) -> Result<(), UnthBufError> {
+        self.check_bitset()?;
+        other.check_bitset()?;
+        if self.capacity != other.capacity {
+            return Err(UnthBufError::LengthMismatch { expected: self.capacity, actual: other.capacity })
+        }
+        Ok(())
+    }
+    
+    /// Returns an iterator over the cells of both buffers, which must have the same shape, and their valid-bit masks.
+    fn zip_cells<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = (usize, usize, usize)> + 'a {
+        self.iter_cells().zip(other.data.iter()).map(|((lhs, mask), &rhs)| (lhs, rhs, mask))
+    }
+}
+
+#[cfg(feature = "index-set")]
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns the ascending indices of all set elements of a bitset; the sparse complement to [`Self::to_bool_vec`].
+    /// 
+    /// # Errors
+    /// - If the `bits`-size is not `1`.
+    pub fn to_index_set(&self) -> Result<Vec<usize>, UnthBufError> {
+        self.check_bitset()?;
+        
+        Ok(self.copy_range(self.get_indices())
+            .enumerate()
+            .filter_map(|(index, value)| (value == 1).then_some(index))
+            .collect())
+    }
+    
+    /// Creates a new bitset of the given `capacity`, i.e. with a `bits`-size of `1`, setting the given indices.
+    /// 
+    /// # Errors
+    /// - If the given `capacity` is `0`.
+    /// - If any index is not within `0..capacity`, reporting the first one.
+    pub fn from_index_set(capacity: usize, indices: &[usize]) -> Result<Self, UnthBufError> {
+        if capacity == 0 {return Err(UnthBufError::ZeroCapacity)}
+        if let Some(&index) = indices.iter().find(|&&index| index >= capacity) {
+            return Err(UnthBufError::RangeOutOfBounds { start: index, end: index.saturating_add(1), capacity })
+        }
+        
+        let mut new = Self::new(Bits::MIN, capacity);
+        for &index in indices {
+            // This is safe, as all indices were checked above, and `1` always fits.
+            unsafe {new.set_unchecked(index, 1)};
+        }
+        Ok(new)
+    }
+}
@@ -0,0 +1,98 @@
+//! Bulk access to [`UnthBuf`]
+use crate::{UnthBuf, UnthBufError, CellLayout};
+#[cfg(feature = "prefetch")]
+use crate::ElementLocation;
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Decodes elements into the given slice, without allocating, returning how many were written.
+    /// 
+    /// If the slice is shorter than the capacity, only the leading `out.len()` elements are decoded.
+    /// 
+    /// The decoding itself is done by [`CellLayout::decode_into`].
+    pub fn decode_into(&self, out: &mut [usize]) -> usize {
+        CL::decode_into(&self.view(), out)
+    }
+    
+    /// Encodes the given values into the leading elements of this buffer, leaving the rest untouched.
+    /// 
+    /// Unlike [`Self::fill_from`], all values are validated before any is written.
+    /// 
+    /// # Errors
+    /// - If there are more values than the capacity.
+    /// - If any value does not fit, reporting the first one.
+    pub fn encode_from_slice(&mut self, values: &[usize]) -> Result<(), UnthBufError> {
+        if values.len() > self.capacity {
+            return Err(UnthBufError::LengthMismatch { expected: self.capacity, actual: values.len() })
+        }
+        
+        if let Some(index) = values.iter().position(|&value| !self.can_element_fit(value)) {
+            return Err(UnthBufError::ValueDoesNotFit { index, value: values[index] })
+        }
+        
+        for (index, &value) in values.iter().enumerate() {
+            // This is safe, as the index is within the capacity.
+            unsafe {self.set_unchecked(index, value)};
+        }
+        
+        Ok(())
+    }
+    
+    /// Reads the elements at the given `indices` into the leading elements of `out`, i.e. `out[k] = self[indices[k]]`.
+    /// 
+    /// All indices are validated before any element is read. With the `prefetch` feature,
+    /// the cell of the next index is prefetched while reading the current one, overlapping the latency of random access.
+    /// 
+    /// # Errors
+    /// - If `out` is shorter than `indices`.
+    /// - If any index is out of bounds, reporting the first one.
+    pub fn gather(&self, indices: &[usize], out: &mut [usize]) -> Result<(), UnthBufError> {
+        if out.len() < indices.len() {
+            return Err(UnthBufError::LengthMismatch { expected: indices.len(), actual: out.len() })
+        }
+        
+        if let Some(&index) = indices.iter().find(|&&index| !self.is_index(index)) {
+            return Err(UnthBufError::RangeOutOfBounds { start: index, end: index.saturating_add(1), capacity: self.capacity })
+        }
+        
+        for (k, &index) in indices.iter().enumerate() {
+            #[cfg(feature = "prefetch")]
+            if let Some(cell) = indices.get(k + 1).and_then(|&next| self.data.get(self.location_of(next).cell())) {
+                crate::iter::prefetch(cell);
+            }
+            
+            // This is safe, as all indices were validated above.
+            out[k] = unsafe {self.get_unchecked(index)};
+        }
+        
+        Ok(())
+    }
+    
+    /// Writes `values[k]` to the element at `indices[k]`, for every `k`; if an index repeats, the later value wins.
+    /// 
+    /// This is all-or-nothing: lengths, indices and values are validated before any element is written.
+    /// 
+    /// # Errors
+    /// - If the amount of values does not match the amount of indices.
+    /// - If any index is out of bounds, reporting the first one.
+    /// - If any value does not fit, reporting the first one along with the index it was meant for.
+    pub fn scatter(&mut self, indices: &[usize], values: &[usize]) -> Result<(), UnthBufError> {
+        if values.len() != indices.len() {
+            return Err(UnthBufError::LengthMismatch { expected: indices.len(), actual: values.len() })
+        }
+        
+        if let Some(&index) = indices.iter().find(|&&index| !self.is_index(index)) {
+            return Err(UnthBufError::RangeOutOfBounds { start: index, end: index.saturating_add(1), capacity: self.capacity })
+        }
+        
+        if let Some(k) = values.iter().position(|&value| !self.can_element_fit(value)) {
+            return Err(UnthBufError::ValueDoesNotFit { index: indices[k], value: values[k] })
+        }
+        
+        for (&index, &value) in indices.iter().zip(values) {
+            // This is safe, as all indices were validated above.
+            unsafe {self.set_unchecked(index, value)};
+        }
+        
+        Ok(())
+    }
+}
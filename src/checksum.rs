@@ -0,0 +1,37 @@
+//! Checksums for [`UnthBuf`]
+use crate::{UnthBuf, CellLayout};
+
+/// The reflected CRC-32 (IEEE 802.3) polynomial.
+const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320;
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Computes a CRC-32 checksum of the bit-size, capacity and elements of this buffer.
+    /// 
+    /// Padding bits are *not* included, and all values are hashed as little-endian `u64`s,
+    /// so the checksum is stable across layouts, pointer-widths and platforms.
+    pub fn checksum(&self) -> u32 {
+        let mut crc = !0u32;
+        
+        crc = crc32_update(crc, &[self.bits.get()]);
+        crc = crc32_update(crc, &(self.capacity as u64).to_le_bytes());
+        for index in self.get_indices() {
+            // This is safe, as the index comes from the range of valid indices.
+            let element = unsafe {self.get_unchecked(index)};
+            crc = crc32_update(crc, &(element as u64).to_le_bytes());
+        }
+        
+        !crc
+    }
+}
+
+/// Feeds the given bytes into a running CRC-32.
+fn crc32_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (CRC32_POLYNOMIAL & mask);
+        }
+    }
+    crc
+}
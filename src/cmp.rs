@@ -0,0 +1,115 @@
+//! Comparison for [`UnthBuf`]
+use crate::{UnthBuf, UnthBufError, CellLayout};
+use core::cmp::Ordering;
+
+impl<CL: CellLayout> PartialEq for UnthBuf<CL> {
+    /// Two buffers are equal if they hold the same elements, with the same bit-size.
+    /// 
+    /// Padding bits are *not* compared.
+    fn eq(&self, other: &Self) -> bool {
+        if self.capacity != other.capacity || self.bits != other.bits {
+            return false;
+        }
+        
+        self.get_indices().all(|index| unsafe {
+            // This is safe, as both buffers have the same capacity.
+            self.get_unchecked(index) == other.get_unchecked(index)
+        })
+    }
+}
+
+impl<CL: CellLayout> Eq for UnthBuf<CL> {}
+
+impl<CL: CellLayout> PartialOrd for UnthBuf<CL> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<CL: CellLayout> Ord for UnthBuf<CL> {
+    /// Compares the elements of both buffers lexicographically, then their capacity, then their bit-size.
+    /// 
+    /// Elements are decoded lazily, stopping at the first differing position;
+    /// buffers with different bit-sizes are still comparable by their values.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let shared = self.capacity.min(other.capacity);
+        
+        for index in 0..shared {
+            // This is safe, as the index is within the capacity of both buffers.
+            let (lhs, rhs) = unsafe {
+                (self.get_unchecked(index), other.get_unchecked(index))
+            };
+            
+            match lhs.cmp(&rhs) {
+                Ordering::Equal => continue,
+                ordering => return ordering
+            }
+        }
+        
+        self.capacity.cmp(&other.capacity)
+            .then_with(|| self.bits.cmp(&other.bits))
+    }
+}
+
+impl<CL: CellLayout> PartialEq<[usize]> for UnthBuf<CL> {
+    /// A buffer is equal to a slice if it holds the same elements, in the same order.
+    fn eq(&self, other: &[usize]) -> bool {
+        self.capacity == other.len() && self.copy_range(0..self.capacity).eq(other.iter().copied())
+    }
+}
+
+impl<CL: CellLayout> PartialEq<Vec<usize>> for UnthBuf<CL> {
+    fn eq(&self, other: &Vec<usize>) -> bool {
+        *self == **other
+    }
+}
+
+impl<CL: CellLayout> PartialEq<UnthBuf<CL>> for [usize] {
+    fn eq(&self, other: &UnthBuf<CL>) -> bool {
+        *other == *self
+    }
+}
+
+impl<CL: CellLayout> PartialEq<UnthBuf<CL>> for Vec<usize> {
+    fn eq(&self, other: &UnthBuf<CL>) -> bool {
+        *other == **self
+    }
+}
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Counts the positions at which the elements of both buffers differ.
+    /// 
+    /// # Errors
+    /// - If the bit-sizes or capacities of both buffers do not match.
+    pub fn element_difference_count(&self, other: &Self) -> Result<usize, UnthBufError> {
+        self.check_same_shape(other)?;
+        Ok(self.zip_elements(other).filter(|(lhs, rhs)| lhs != rhs).count())
+    }
+    
+    /// Counts the bits in which the elements of both buffers differ, i.e. their hamming distance.
+    /// 
+    /// Padding bits are *not* compared.
+    /// 
+    /// # Errors
+    /// - If the bit-sizes or capacities of both buffers do not match.
+    pub fn bit_difference_count(&self, other: &Self) -> Result<usize, UnthBufError> {
+        self.check_same_shape(other)?;
+        Ok(self.zip_elements(other).map(|(lhs, rhs)| (lhs ^ rhs).count_ones() as usize).sum())
+    }
+    
+    /// Checks that both buffers have the same bit-size and capacity.
+    fn check_same_shape(&self, other: &Self) -> Result<(), UnthBufError> {
+        if self.bits != other.bits {
+            return Err(UnthBufError::BitsMismatch { expected: self.bits, actual: other.bits })
+        }
+        if self.capacity != other.capacity {
+            return Err(UnthBufError::LengthMismatch { expected: self.capacity, actual: other.capacity })
+        }
+        Ok(())
+    }
+    
+    /// Returns an iterator over the pairs of elements of both buffers, which must have the same capacity.
+    fn zip_elements<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.copy_range(self.get_indices()).zip(other.copy_range(other.get_indices()))
+    }
+}
@@ -0,0 +1,81 @@
+//! Conformance checks for [`CellLayout`] implementations.
+use crate::{UnthBuf, CellLayout, ElementLocation, Bits, BITS_PER_CELL};
+
+/// Checks that the [`CellLayout`] `CL` upholds the contract the rest of this crate relies on, panicking if it does not.
+/// 
+/// The checks run on a buffer of the given `bits`-size and `capacity`:
+/// - New buffers are zeroed, and have exactly [`CellLayout::get_cell_count`] cells.
+/// - Every element is located in an existing cell, see [`ElementLocation::cell`].
+/// - Every element round-trips through `set`/`get` without disturbing its neighbours.
+/// - Out-of-bounds indices and values that do not fit are rejected.
+/// - Filling stores the value into every element, and [`CellLayout::decode_into`] agrees with `get`.
+/// - Padding bits stay cleared after every write, i.e. [`UnthBuf::validate`] passes.
+/// 
+/// Third-party layouts should run this for several bit-sizes, including ones that do not divide the cell size.
+/// 
+/// # Panic
+/// - Panics with a description of the first violation found.
+/// - Panics if `capacity` is `0`, or if `bits` is larger than a cell.
+pub fn check_cell_layout_conformance<CL: CellLayout>(bits: Bits, capacity: usize) {
+    assert!(capacity != 0, "capacity must not be 0");
+    assert!(bits.get() <= BITS_PER_CELL, "{bits} bits do not fit into a cell");
+    
+    let mut buf = UnthBuf::<CL>::new(bits, capacity);
+    let mask = buf.get_element_mask();
+    assert_eq!(buf.raw_len(), CL::get_cell_count(capacity, bits), "new buffer has the wrong amount of cells");
+    assert!(buf.validate().is_ok(), "new buffer is not valid: {:?}", buf.validate());
+    assert!(buf.get_indices().all(|index| buf.get(index) == Some(0)), "new buffer is not zeroed");
+    
+    let exact = CL::get_exact_bit_count(&buf);
+    assert!(exact >= capacity * bits.get() as usize, "exact bit count {exact} cannot hold all elements");
+    assert!(exact <= buf.get_total_bit_count(), "exact bit count {exact} exceeds the total bit count");
+    
+    // Locations
+    for index in buf.get_indices() {
+        let cell = buf.location_of(index).cell();
+        assert!(cell < buf.raw_len(), "element {index} is located in cell {cell}, which does not exist");
+    }
+    
+    // Bounds
+    assert_eq!(buf.get(capacity), None, "index {capacity} is out of bounds, but get returned an element");
+    assert!(buf.set(capacity, 0).is_err(), "index {capacity} is out of bounds, but set accepted it");
+    if mask != usize::MAX {
+        assert!(buf.set(0, mask + 1).is_err(), "value {} does not fit, but set accepted it", mask + 1);
+    }
+    
+    // Round-trips of scrambled values, written forwards and checked afterwards, so clobbered neighbours show up.
+    let scrambled = |index: usize| ((index as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 7) as usize & mask;
+    for index in buf.get_indices() {
+        buf.set(index, scrambled(index)).expect("valid index and fitting value");
+        assert_eq!(buf.get(index), Some(scrambled(index)), "element {index} did not round-trip");
+    }
+    assert!(buf.validate().is_ok(), "writing elements dirtied the buffer: {:?}", buf.validate());
+    for index in buf.get_indices() {
+        assert_eq!(buf.get(index), Some(scrambled(index)), "element {index} was clobbered by a later write");
+    }
+    
+    // Alternating all-ones and all-zeroes, written backwards, so every element borders on opposite bits.
+    for index in buf.get_indices().rev() {
+        buf.set(index, if index % 2 == 0 {mask} else {0}).expect("valid index and fitting value");
+    }
+    for index in buf.get_indices() {
+        assert_eq!(buf.get(index), Some(if index % 2 == 0 {mask} else {0}), "element {index} was clobbered by a later write");
+    }
+    assert!(buf.validate().is_ok(), "writing elements dirtied the buffer: {:?}", buf.validate());
+    
+    // Filling and decoding
+    for value in [0, mask, 0x5555_5555_5555_5555u64 as usize & mask, 1] {
+        buf.fill_with(value);
+        assert!(buf.validate().is_ok(), "filling with {value} dirtied the buffer: {:?}", buf.validate());
+        assert!(buf.get_indices().all(|index| buf.get(index) == Some(value)), "filling with {value} missed elements");
+        
+        let mut out = vec![usize::MAX; capacity];
+        assert_eq!(CL::decode_into(&buf.view(), &mut out), capacity, "decode_into did not decode every element");
+        assert!(out.iter().all(|&element| element == value), "decode_into disagrees with get after filling with {value}");
+    }
+    
+    // Padding: with every element at its maximum, only the bits of the elements themselves may be set.
+    buf.fill_with(mask);
+    let set_bits: usize = buf.raw().iter().map(|cell| cell.count_ones() as usize).sum();
+    assert_eq!(set_bits, capacity * bits.get() as usize, "cells hold bits beyond the elements");
+}
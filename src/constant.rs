@@ -0,0 +1,154 @@
+//! [`UnthBuf`] with a compile-time bit-size.
+use crate::{UnthBuf, UnthBufRef, UnthBufMut, UnthBufError, CellLayout, Bits, BITS_PER_CELL};
+
+/// An [`UnthBuf`] whose bit-size `BITS` is known at compile time.
+/// 
+/// Since the bit-size, mask and elements-per-cell are constants,
+/// the compiler can fold the shifts and masks of every `get`/`set`.
+/// 
+/// Using a `BITS` of `0` or more than [`usize::BITS`] fails to compile.
+#[derive(Clone)]
+pub struct UnthBufConst<const BITS: u8, CL: CellLayout> {
+    /// The underlying buffer, whose bit-size is always `BITS`.
+    pub(crate) inner: UnthBuf<CL>,
+}
+
+impl<const BITS: u8, CL: CellLayout> UnthBufConst<BITS, CL> {
+    /// The bit-size of the elements.
+    pub const BITS: Bits = match Bits::new(BITS) {
+        Some(bits) if BITS <= BITS_PER_CELL => bits,
+        _ => panic!("bit-size must be within 1..=usize::BITS")
+    };
+    
+    /// The mask of bits covering a single element.
+    const MASK: usize = if BITS == BITS_PER_CELL {usize::MAX} else {(1 << BITS) - 1};
+    
+    /// The amount of elements per cell.
+    const ELPC: u8 = BITS_PER_CELL / Self::BITS.get();
+    
+    /// Creates a new [`UnthBufConst`] with the given `capacity`, filled with `0`.
+    /// 
+    /// # Panic
+    /// - Panics if the given `capacity` is `0`.
+    pub fn new(capacity: usize) -> Self {
+        Self { inner: UnthBuf::new(Self::BITS, capacity) }
+    }
+    
+    /// Returns a reference to the underlying runtime-sized [`UnthBuf`].
+    #[inline(always)]
+    pub fn as_unthbuf(&self) -> &UnthBuf<CL> {
+        &self.inner
+    }
+    
+    /// Gets how many elements are stored within.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.inner.capacity
+    }
+    
+    /// Always `false`, as a buffer cannot be of 0 capacity.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    
+    /// Checks if the given value can be stored in this buffer.
+    #[inline(always)]
+    pub fn can_element_fit(&self, value: usize) -> bool {
+        (value & Self::MASK) == value
+    }
+    
+    /// Is the given index (`0..self.len()`) valid for this buffer?
+    #[inline(always)]
+    pub fn is_index(&self, index: usize) -> bool {
+        index < self.inner.capacity
+    }
+    
+    /// Returns the element at the given `index`.
+    /// 
+    /// Out-of-bounds access will return [`Option::None`].
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<usize> {
+        if !self.is_index(index) {return None}
+        Some(unsafe {self.get_unchecked(index)})
+    }
+    
+    /// Returns the element at the given `index`, *without* checking bounds.
+    /// 
+    /// # Safety
+    /// If the index is not within `0..self.len()`, this function will cause undefined behaviour.
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, index: usize) -> usize {
+        let view = UnthBufRef {
+            capacity: self.inner.capacity,
+            data: &self.inner.data,
+            bits: Self::BITS,
+            mask: Self::MASK,
+            elpc: Self::ELPC,
+            cell_layout: core::marker::PhantomData,
+        };
+        CL::get_unchecked(&view, index)
+    }
+    
+    /// Tries to set the element at the given `index` to the provided `value`.
+    /// 
+    /// # Errors
+    /// - If the value does not fit; check with [`Self::can_element_fit`].
+    /// - If the index is out of bounds; check with [`Self::is_index`].
+    #[inline]
+    pub fn set(&mut self, index: usize, value: usize) -> Result<(),&'static str> {
+        if !self.can_element_fit(value) {return Err("value does not fit")}
+        if !self.is_index(index) {return Err("index is out-of-bounds")}
+        unsafe {self.set_unchecked(index, value);}
+        Ok(())
+    }
+    
+    /// Set the element at the given `index` to the provided `value`, *without* checking bounds.
+    /// 
+    /// # Safety
+    /// If the index is not within `0..self.len()`, this function will cause undefined behaviour.
+    #[inline(always)]
+    pub unsafe fn set_unchecked(&mut self, index: usize, value: usize) {
+        let mut view = UnthBufMut {
+            capacity: self.inner.capacity,
+            data: &mut self.inner.data,
+            bits: Self::BITS,
+            mask: Self::MASK,
+            elpc: Self::ELPC,
+            cell_layout: core::marker::PhantomData,
+        };
+        CL::set_unchecked(&mut view, index, value);
+    }
+}
+
+impl<const BITS: u8, CL: CellLayout> From<UnthBufConst<BITS, CL>> for UnthBuf<CL> {
+    fn from(buf: UnthBufConst<BITS, CL>) -> Self {
+        buf.inner
+    }
+}
+
+impl<const BITS: u8, CL: CellLayout> TryFrom<UnthBuf<CL>> for UnthBufConst<BITS, CL> {
+    type Error = UnthBufError;
+    
+    /// Wraps the given buffer, if its bit-size is `BITS`.
+    fn try_from(buf: UnthBuf<CL>) -> Result<Self, Self::Error> {
+        if buf.bits != Self::BITS {
+            return Err(UnthBufError::BitsMismatch { expected: Self::BITS, actual: buf.bits })
+        }
+        Ok(Self { inner: buf })
+    }
+}
+
+impl<const BITS: u8, CL: CellLayout> PartialEq for UnthBufConst<BITS, CL> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<const BITS: u8, CL: CellLayout> Eq for UnthBufConst<BITS, CL> {}
+
+impl<const BITS: u8, CL: CellLayout> core::fmt::Debug for UnthBufConst<BITS, CL> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
@@ -0,0 +1,68 @@
+//! Sequential reading of [`UnthBuf`]s.
+use crate::{UnthBuf, CellLayout};
+
+/// A stateful reader over the elements of an [`UnthBuf`], tracking the position of the next element to read.
+/// 
+/// Unlike an iterator, the position can be moved freely via [`Self::skip`] and [`Self::seek`].
+#[derive(Clone, Copy)]
+pub struct UnthBufCursor<'a, CL: CellLayout> {
+    /// The buffer being read.
+    pub(crate) buf: &'a UnthBuf<CL>,
+    
+    /// The index of the next element to read; never beyond the capacity of the buffer.
+    pub(crate) pos: usize,
+}
+
+impl<'a, CL: CellLayout> UnthBufCursor<'a, CL> {
+    /// Creates a new [`UnthBufCursor`], positioned at the first element of the given buffer.
+    pub fn new(buf: &'a UnthBuf<CL>) -> Self {
+        Self { buf, pos: 0 }
+    }
+    
+    /// Returns the next element and advances the cursor past it.
+    /// 
+    /// Once all elements were read, this will return [`Option::None`].
+    #[inline]
+    pub fn read(&mut self) -> Option<usize> {
+        let value = self.buf.get(self.pos)?;
+        self.pos += 1;
+        Some(value)
+    }
+    
+    /// Advances the cursor by `n` elements, stopping at the end of the buffer.
+    #[inline]
+    pub fn skip(&mut self, n: usize) {
+        self.pos = self.pos.saturating_add(n).min(self.buf.capacity);
+    }
+    
+    /// Moves the cursor to the given position, clamped to the end of the buffer.
+    #[inline]
+    pub fn seek(&mut self, pos: usize) {
+        self.pos = pos.min(self.buf.capacity);
+    }
+    
+    /// Gets the position of the next element to read.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+    
+    /// Gets how many elements are left to read.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize {
+        self.buf.capacity - self.pos
+    }
+}
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns a [`UnthBufCursor`], positioned at the first element of this buffer.
+    pub fn cursor(&self) -> UnthBufCursor<'_, CL> {
+        UnthBufCursor::new(self)
+    }
+}
+
+impl<CL: CellLayout> core::fmt::Debug for UnthBufCursor<'_, CL> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "UnthBufCursor({}/{})", self.pos, self.buf.capacity)
+    }
+}
@@ -0,0 +1,89 @@
+//! Alternative encodings of an [`UnthBuf`]
+use crate::{UnthBuf, UnthBufError, CellLayout, Bits};
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Encodes the elements of this buffer as a list of `(value, run_length)` pairs.
+    pub fn to_rle(&self) -> Vec<(usize, usize)> {
+        let mut runs: Vec<(usize, usize)> = Vec::new();
+        
+        for index in self.get_indices() {
+            // This is safe, as the index comes from the range of valid indices.
+            let element = unsafe {self.get_unchecked(index)};
+            match runs.last_mut() {
+                Some((value, length)) if *value == element => *length += 1,
+                _ => runs.push((element, 1))
+            }
+        }
+        
+        runs
+    }
+    
+    /// Creates a new [`UnthBuf`] with the given `bits`-size from a list of `(value, run_length)` pairs.
+    /// 
+    /// # Errors
+    /// - If any value does not fit into `bits`.
+    /// - If the total length of all runs is `0`.
+    /// - If the total length of all runs, or their total amount of bits, overflows an [`usize`].
+    pub fn from_rle(bits: Bits, runs: &[(usize, usize)]) -> Result<Self, UnthBufError> {
+        let mask = Self::mask_from_bits(bits.get());
+        
+        let mut capacity = 0usize;
+        for &(value, length) in runs {
+            if value & mask != value {
+                return Err(UnthBufError::ValueDoesNotFit { index: capacity, value })
+            }
+            capacity = capacity.checked_add(length).ok_or(UnthBufError::CapacityOverflow { capacity: usize::MAX })?;
+        }
+        
+        Self::checked_cell_count(bits, capacity)?;
+        
+        let elements = runs.iter().flat_map(|&(value, length)| core::iter::repeat_n(value, length));
+        Ok(Self::new_from_capacity_and_iter(bits, capacity, elements))
+    }
+    
+    /// Encodes the elements of this ascending buffer as its first element,
+    /// followed by a new buffer of the differences between every element and its predecessor.
+    /// 
+    /// The first element is its own predecessor, so the first difference is always `0`
+    /// and the new buffer has the same capacity, even if that is `1`.
+    /// The bit-size of the new buffer is the smallest that fits the largest difference.
+    /// 
+    /// # Errors
+    /// - If any element is smaller than its predecessor, as negative differences cannot be stored.
+    pub fn to_deltas(&self) -> Result<(usize, UnthBuf<CL>), UnthBufError> {
+        let mut deltas = Vec::with_capacity(self.capacity);
+        // This is safe, as the capacity is never 0.
+        let first = unsafe {self.get_unchecked(0)};
+        let mut previous = first;
+        for index in 0..self.capacity {
+            // This is safe, as the index is within the range of valid indices.
+            let element = unsafe {self.get_unchecked(index)};
+            match element.checked_sub(previous) {
+                Some(delta) => deltas.push(delta),
+                None => return Err(UnthBufError::NotAscending { index })
+            }
+            previous = element;
+        }
+        
+        let bits = Self::bits_for_value(deltas.iter().copied().max().unwrap_or(0));
+        Ok((first, UnthBuf::new_from_sized_iter(bits, deltas.into_iter())))
+    }
+    
+    /// Decodes a buffer of differences created by [`Self::to_deltas`], starting at `first`,
+    /// into a new buffer with the same capacity and the given `bits`-size.
+    /// 
+    /// Passing the bit-size of the encoded buffer restores a buffer equal to it.
+    /// 
+    /// # Panic
+    /// - Panics if the sum of `first` and the differences overflows an [`usize`].
+    /// - Panics if any decoded element does not fit in `bits`, naming the offending index.
+    pub fn from_deltas(bits: Bits, first: usize, deltas: &UnthBuf<CL>) -> Self {
+        let mut current = first;
+        Self::new_from_fn(bits, deltas.capacity, |index| {
+            // This is safe, as both buffers have the same capacity.
+            let delta = unsafe {deltas.get_unchecked(index)};
+            current = current.checked_add(delta).expect("sum of deltas overflows usize");
+            current
+        })
+    }
+}
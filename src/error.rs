@@ -0,0 +1,102 @@
+//! Errors for [`UnthBuf`](crate::UnthBuf)
+use crate::Bits;
+
+/// An error that can occur when constructing or modifying an [`UnthBuf`](crate::UnthBuf).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnthBufError {
+    /// A buffer with a capacity of `0` was requested.
+    ZeroCapacity,
+    
+    /// The given backing buffer does not have the amount of cells the layout requires.
+    CellCountMismatch {
+        /// The amount of cells the layout requires.
+        expected: usize,
+        /// The amount of cells that were given.
+        actual: usize,
+    },
+    
+    /// The length of a given slice is not what was expected.
+    LengthMismatch {
+        /// The expected length, or the bound that was violated.
+        expected: usize,
+        /// The given length.
+        actual: usize,
+    },
+    
+    /// A value does not fit into the bit-size of a buffer.
+    ValueDoesNotFit {
+        /// The index at which the value was to be stored.
+        index: usize,
+        /// The offending value.
+        value: usize,
+    },
+    
+    /// An element of a buffer is smaller than its predecessor.
+    NotAscending {
+        /// The index of the offending element.
+        index: usize,
+    },
+    
+    /// A cell of a buffer has padding bits set.
+    DirtyPadding {
+        /// The index of the offending cell.
+        cell: usize,
+    },
+    
+    /// The given range does not lie within the capacity of a buffer.
+    RangeOutOfBounds {
+        /// The start of the range.
+        start: usize,
+        /// The (exclusive) end of the range; for a single index, the index plus one, saturated at [`usize::MAX`].
+        end: usize,
+        /// The capacity of the buffer.
+        capacity: usize,
+    },
+    
+    /// The bit-sizes of two buffers do not match.
+    BitsMismatch {
+        /// The bit-size that was expected.
+        expected: Bits,
+        /// The bit-size that was given.
+        actual: Bits,
+    },
+    
+    /// The given bytes are not aligned to, or not a whole multiple of, the size of a cell.
+    MisalignedBytes,
+    
+    /// A bit-size is `0` or larger than a cell.
+    InvalidBits {
+        /// The offending bit-size.
+        bits: u8,
+    },
+    
+    /// The requested capacity, or its total amount of bits, does not fit into an [`usize`].
+    CapacityOverflow {
+        /// The offending capacity, or [`usize::MAX`] if the capacity itself overflowed.
+        capacity: usize,
+    },
+    
+    /// A pattern to repeat is empty.
+    EmptyPattern,
+}
+
+impl core::fmt::Display for UnthBufError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ZeroCapacity => write!(f, "cannot create buffer of 0 capacity"),
+            Self::CellCountMismatch { expected, actual } => write!(f, "expected {expected} cells, but got {actual}"),
+            Self::LengthMismatch { expected, actual } => write!(f, "expected a length of {expected}, but got {actual}"),
+            Self::ValueDoesNotFit { index, value } => write!(f, "value 0x{value:X} at index {index} does not fit"),
+            Self::NotAscending { index } => write!(f, "element at index {index} is smaller than its predecessor"),
+            Self::DirtyPadding { cell } => write!(f, "cell {cell} has padding bits set"),
+            Self::RangeOutOfBounds { start, end, capacity } => write!(f, "range {start}..{end} is out of bounds for capacity {capacity}"),
+            Self::BitsMismatch { expected, actual } => write!(f, "expected {expected}-bit elements, but got {actual}-bit elements"),
+            Self::MisalignedBytes => write!(f, "bytes are not aligned to or sized in whole cells"),
+            Self::InvalidBits { bits } => write!(f, "{bits} is not a valid bit-size for elements"),
+            Self::CapacityOverflow { capacity } => write!(f, "the bits of {capacity} elements overflow usize"),
+            Self::EmptyPattern => write!(f, "cannot repeat an empty pattern"),
+        }
+    }
+}
+
+impl std::error::Error for UnthBufError {}
@@ -1,5 +1,8 @@
 //! Formatting for [`UnthBuf`]
-use super::{UnthBuf, CellLayout};
+use super::{UnthBuf, CellLayout, BITS_PER_CELL};
+
+/// The amount of leading and trailing elements shown by the [`core::fmt::Debug`] output of an [`UnthBuf`].
+const DEBUG_SAMPLE: usize = 8;
 
 impl<CL: CellLayout> core::fmt::Debug for UnthBuf<CL> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -15,6 +18,26 @@ impl<CL: CellLayout> core::fmt::Debug for UnthBuf<CL> {
             .field("bits", &self.bits)
             .field("mask", &self.mask)
             //.field("data", &self.data)
+            .field("elements", &ElementSample(self))
+            .finish()
+    }
+}
+
+/// Lists the first and last [`DEBUG_SAMPLE`] elements of an [`UnthBuf`], with an ellipsis in between if there are more.
+struct ElementSample<'a, CL: CellLayout>(&'a UnthBuf<CL>);
+
+impl<CL: CellLayout> core::fmt::Debug for ElementSample<'_, CL> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let buf = self.0;
+        let mut list = f.debug_list();
+        
+        if buf.capacity <= DEBUG_SAMPLE * 2 {
+            return list.entries(buf.copy_range(buf.get_indices())).finish()
+        }
+        
+        list.entries(buf.copy_range(0..DEBUG_SAMPLE))
+            .entry(&format_args!("..."))
+            .entries(buf.copy_range(buf.capacity - DEBUG_SAMPLE..buf.capacity))
             .finish()
     }
 }
@@ -23,7 +46,95 @@ impl<CL: CellLayout + 'static> core::fmt::Display for UnthBuf<CL> {
     /// Prints the [`UnthBuf`] as a list of numbers, with the bit-size and capacity at the start...
     /// 
     /// ...thus taking the form: `[uBITS; CAPACITY; ELEMENT, ... ELEMENT]`
+    /// 
+    /// The alternate form (`{:#}`) instead prints one element per line, prefixed by its index.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if !f.alternate() {
+            return self.fmt_elements(f, |f, element| write!(f, "{element}"))
+        }
+        
+        let index_width = (self.capacity - 1).to_string().len();
+        writeln!(f, "[u{}; {};", self.bits, self.capacity)?;
+        for (index, element) in self.iter().enumerate() {
+            writeln!(f, "    {index:>index_width$}: {element}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Prints the elements of an [`UnthBuf`] joined by the given separator, without brackets or a header.
+/// 
+/// This is handy for dumping large buffers to logs, e.g. as `Separated(&buf, "\n")`.
+#[derive(Clone, Copy)]
+pub struct Separated<'a, CL: CellLayout>(pub &'a UnthBuf<CL>, pub &'a str);
+
+impl<CL: CellLayout + 'static> core::fmt::Display for Separated<'_, CL> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (index, element) in self.0.iter().enumerate() {
+            if index != 0 {
+                f.write_str(self.1)?;
+            }
+            write!(f, "{element}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<CL: CellLayout + 'static> core::fmt::LowerHex for UnthBuf<CL> {
+    /// Prints the [`UnthBuf`] like [`core::fmt::Display`] does, but with lowercase hexadecimal elements.
+    /// 
+    /// Each element is zero-padded to the amount of nibbles needed for the bit-size.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let width = self.bits.get().div_ceil(4) as usize;
+        self.fmt_elements(f, |f, element| write!(f, "{element:0width$x}"))
+    }
+}
+
+impl<CL: CellLayout + 'static> core::fmt::UpperHex for UnthBuf<CL> {
+    /// Prints the [`UnthBuf`] like [`core::fmt::Display`] does, but with uppercase hexadecimal elements.
+    /// 
+    /// Each element is zero-padded to the amount of nibbles needed for the bit-size.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let width = self.bits.get().div_ceil(4) as usize;
+        self.fmt_elements(f, |f, element| write!(f, "{element:0width$X}"))
+    }
+}
+
+impl<CL: CellLayout + 'static> core::fmt::Binary for UnthBuf<CL> {
+    /// Prints the [`UnthBuf`] like [`core::fmt::Display`] does, but with binary elements.
+    /// 
+    /// Each element is prefixed with `0b` and zero-padded to the bit-size.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let width = self.bits.get() as usize;
+        self.fmt_elements(f, |f, element| write!(f, "0b{element:0width$b}"))
+    }
+}
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Dumps the raw cells of this buffer as zero-padded binary, one cell per line, prefixed by the cell index.
+    /// 
+    /// This is a diagnostic helper for inspecting the layout of elements; it is *not* fast.
+    pub fn debug_cells(&self) -> String {
+        use core::fmt::Write;
+        
+        let index_width = (self.data.len() - 1).to_string().len();
+        let cell_width = BITS_PER_CELL as usize;
+        
+        let mut out = String::with_capacity(self.data.len() * (index_width + cell_width + 4));
+        for (index, cell) in self.data.iter().enumerate() {
+            // Writing into a String cannot fail.
+            let _ = writeln!(out, "#{index:0index_width$}: {cell:0cell_width$b}");
+        }
+        out
+    }
+}
+
+impl<CL: CellLayout + 'static> UnthBuf<CL> {
+    /// Writes the [`UnthBuf`] in the form `[uBITS; CAPACITY; ELEMENT, ... ELEMENT]`,
+    /// using the given function to write each element.
+    fn fmt_elements<F>(&self, f: &mut core::fmt::Formatter<'_>, element_fmt: F) -> core::fmt::Result
+        where F: Fn(&mut core::fmt::Formatter<'_>, usize) -> core::fmt::Result
+    {
         write!(f, "[")?;
         write!(f, "u{}", self.bits)?;
         write!(f, "; ")?;
@@ -32,11 +143,11 @@ impl<CL: CellLayout + 'static> core::fmt::Display for UnthBuf<CL> {
         let mut comma = false;
         for element in self {
             if comma {
-                write!(f, ", {element}")?;
+                write!(f, ", ")?;
             } else {
-                write!(f, "{element}")?;
                 comma = true;
             }
+            element_fmt(f, element)?;
         }
         write!(f, "]")
     }
@@ -0,0 +1,205 @@
+//! Growable [`UnthBuf`]
+use crate::{UnthBuf, UnthBufError, CellLayout, Bits};
+
+/// A [`UnthBuf`] that can be appended to, like a [`Vec`].
+/// 
+/// The backing buffer grows in batches, doubling its capacity whenever it runs out of room;
+/// if widening is enabled via [`Self::with_widening`], the bit-size grows to fit pushed values as well.
+#[derive(Clone)]
+pub struct GrowableUnthBuf<CL: CellLayout> {
+    /// The backing buffer; only the first [`Self::len`] elements are in use.
+    pub(crate) inner: UnthBuf<CL>,
+    
+    /// How many elements are in use.
+    pub(crate) len: usize,
+    
+    /// Whether the bit-size grows to fit pushed values.
+    pub(crate) widen: bool,
+}
+
+impl<CL: CellLayout> GrowableUnthBuf<CL> {
+    /// Creates a new, empty [`GrowableUnthBuf`] with the given `bits`-size, with room for `capacity` elements.
+    /// 
+    /// A `capacity` of `0` is rounded up to `1`, as the backing buffer cannot be of 0 capacity.
+    pub fn new(bits: Bits, capacity: usize) -> Self {
+        Self {
+            inner: UnthBuf::new(bits, capacity.max(1)),
+            len: 0,
+            widen: false,
+        }
+    }
+    
+    /// Enables or disables widening the bit-size to fit pushed values.
+    pub fn with_widening(mut self, widen: bool) -> Self {
+        self.widen = widen;
+        self
+    }
+    
+    /// Gets how many elements are stored within.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    /// Returns `true` if no elements are stored within.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    /// Gets how many elements can be stored before the backing buffer must grow.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+    
+    /// Returns the bit-size of the individual elements.
+    #[inline(always)]
+    pub fn get_element_bits(&self) -> Bits {
+        self.inner.bits
+    }
+    
+    /// Returns the element at the given `index`.
+    /// 
+    /// Out-of-bounds access, beyond [`Self::len`], will return [`Option::None`].
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<usize> {
+        if index >= self.len {return None}
+        self.inner.get(index)
+    }
+    
+    /// Returns an iterator that yields all stored elements.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.inner.copy_range(0..self.len)
+    }
+    
+    /// Ensures there is room for at least `additional` more elements, growing the backing buffer at most once.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len + additional;
+        if required <= self.inner.capacity {return}
+        
+        let capacity = required.max(self.inner.capacity * 2);
+        self.inner = UnthBuf::new_from_capacity_and_iter(self.inner.bits, capacity, self.inner.copy_range(0..self.len));
+    }
+    
+    /// Appends the given value, growing the backing buffer if required.
+    /// 
+    /// # Errors
+    /// - If the value does not fit and widening is disabled.
+    pub fn push(&mut self, value: usize) -> Result<(), UnthBufError> {
+        self.make_fit(self.len, value)?;
+        
+        self.reserve(1);
+        // This is safe, as the backing buffer has room for at least one more element, and the value fits.
+        unsafe {self.inner.set_unchecked(self.len, value)};
+        self.len += 1;
+        Ok(())
+    }
+    
+    /// Inserts the given value at `index`, shifting all elements after it to the right.
+    /// 
+    /// This preserves ordering, but is *O(n)*; grows the backing buffer if required.
+    /// 
+    /// # Errors
+    /// - If the value does not fit and widening is disabled.
+    /// 
+    /// # Panic
+    /// - Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: usize) -> Result<(), UnthBufError> {
+        assert!(index <= self.len, "insertion index (is {index}) should be <= len (is {})", self.len);
+        self.make_fit(index, value)?;
+        
+        self.reserve(1);
+        // This is safe, as all indices are at most the old length, which is within the grown backing buffer.
+        unsafe {
+            for moved in (index..self.len).rev() {
+                self.inner.set_unchecked(moved + 1, self.inner.get_unchecked(moved));
+            }
+            self.inner.set_unchecked(index, value);
+        }
+        
+        self.len += 1;
+        Ok(())
+    }
+    
+    /// Removes the element at the given `index` and returns it, shifting all elements after it to the left.
+    /// 
+    /// This preserves ordering, but is *O(n)*.
+    /// 
+    /// Out-of-bounds access, beyond [`Self::len`], will return [`Option::None`].
+    pub fn remove(&mut self, index: usize) -> Option<usize> {
+        if index >= self.len {return None}
+        
+        // This is safe, as all indices are below the length, which is within the backing buffer.
+        let removed = unsafe {
+            let removed = self.inner.get_unchecked(index);
+            for moved in index + 1..self.len {
+                self.inner.set_unchecked(moved - 1, self.inner.get_unchecked(moved));
+            }
+            removed
+        };
+        
+        self.len -= 1;
+        Some(removed)
+    }
+    
+    /// Removes the element at the given `index` and returns it, moving the last element into its place.
+    /// 
+    /// This does not preserve ordering, but is *O(1)*.
+    /// 
+    /// Out-of-bounds access, beyond [`Self::len`], will return [`Option::None`].
+    pub fn swap_remove(&mut self, index: usize) -> Option<usize> {
+        if index >= self.len {return None}
+        
+        let last = self.len - 1;
+        // This is safe, as both indices are below the length, which is within the backing buffer.
+        let removed = unsafe {
+            let removed = self.inner.get_unchecked(index);
+            self.inner.set_unchecked(index, self.inner.get_unchecked(last));
+            removed
+        };
+        
+        self.len = last;
+        Some(removed)
+    }
+    
+    /// Ensures the given value, to be stored at `index`, fits; widening the bit-size if enabled.
+    fn make_fit(&mut self, index: usize, value: usize) -> Result<(), UnthBufError> {
+        if self.inner.can_element_fit(value) {return Ok(())}
+        if !self.widen {return Err(UnthBufError::ValueDoesNotFit { index, value })}
+        self.inner.rebit_in_place(UnthBuf::<CL>::bits_for_value(value))
+    }
+    
+    /// Turns this into a [`UnthBuf`] holding exactly the stored elements.
+    /// 
+    /// # Errors
+    /// - If no elements are stored, as the buffer would be of 0 capacity.
+    pub fn into_unthbuf(self) -> Result<UnthBuf<CL>, UnthBufError> {
+        if self.len == 0 {return Err(UnthBufError::ZeroCapacity)}
+        if self.len == self.inner.capacity {return Ok(self.inner)}
+        self.inner.truncate(self.len)
+    }
+}
+
+impl<CL: CellLayout> Extend<usize> for GrowableUnthBuf<CL> {
+    /// Appends all values of the given iterator, reserving room for its lower size-hint up front.
+    /// 
+    /// # Panic
+    /// - If a value does not fit and widening is disabled.
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        
+        for value in iter {
+            if let Err(error) = self.push(value) {
+                panic!("cannot extend buffer: {error}")
+            }
+        }
+    }
+}
+
+impl<CL: CellLayout> core::fmt::Debug for GrowableUnthBuf<CL> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,133 @@
+//! Layout that stores integers as bit-planes, i.e. bit `k` of every element is stored contiguously.
+use crate::{UnthBuf, UnthBufRef, UnthBufMut, CellLayout, ElementLocation, Bits, AlignedUnthBuf, BITS_PER_CELL, convert_layout};
+
+/// Layout that stores integers as bit-planes, i.e. bit `k` of every element is stored contiguously.
+/// 
+/// The cells are split into `bits` planes of `ceil(capacity / BITS_PER_CELL)` cells each;
+/// bit `k` of element `i` is bit `i % BITS_PER_CELL` of cell `i / BITS_PER_CELL` in plane `k`.
+/// 
+/// Accessing a single element touches one cell per plane, which is slow;
+/// in turn, every plane is a plain bitset, which SIMD code can mask and combine a whole cell at a time.
+#[derive(Clone, Copy)]
+pub struct InterleavedLayout;
+
+impl CellLayout for InterleavedLayout {
+    type Location = InterleavedLocation;
+    
+    const ALIGNED: bool = false;
+    
+    #[inline(always)]
+    fn get_cell_count(capacity: usize, bits: Bits) -> usize {
+        get_plane_cell_count(capacity) * bits.get() as usize
+    }
+    
+    fn get_exact_bit_count(buf: &UnthBuf<Self>) -> usize {
+        buf.capacity * buf.bits.get() as usize
+    }
+    
+    fn get_cell_mask(buf: &UnthBufRef<'_, Self>, cell: usize) -> usize {
+        let plane_cells = get_plane_cell_count(buf.capacity);
+        if cell >= plane_cells * buf.bits.get() as usize {return 0}
+        
+        let first = (cell % plane_cells) * BITS_PER_CELL as usize;
+        let elements = (buf.capacity - first).min(BITS_PER_CELL as usize);
+        UnthBuf::<Self>::mask_from_bits(elements as u8)
+    }
+    
+    fn fill(buf: &mut UnthBufMut<'_, Self>, value: usize) {
+        // Every plane is either all ones or all zeroes, depending on the bit of the value.
+        let plane_cells = get_plane_cell_count(buf.capacity);
+        for cell in 0..buf.data.len() {
+            let plane = cell / plane_cells;
+            let mask = Self::get_cell_mask(&buf.view(), cell);
+            buf.data[cell] = if (value >> plane) & 1 == 1 {mask} else {0};
+        }
+    }
+    
+    #[inline(always)]
+    fn location_of(buf: &UnthBufRef<'_, Self>, index: usize) -> Self::Location {
+        InterleavedLocation {
+            cell: index / BITS_PER_CELL as usize,
+            offset: (index % BITS_PER_CELL as usize) as u8,
+            stride: get_plane_cell_count(buf.capacity),
+        }
+    }
+    
+    #[inline(always)]
+    unsafe fn set_unchecked(buf: &mut UnthBufMut<'_, Self>, index: usize, value: usize) {
+        let loc = Self::location_of(&buf.view(), index);
+        
+        for plane in 0..buf.bits.get() as usize {
+            let cell = buf.data.get_unchecked_mut(loc.cell + plane * loc.stride);
+            
+            *cell &= !(1 << loc.offset); // unset the bit of the old value
+            *cell |= ((value >> plane) & 1) << loc.offset; // set bit for new value
+        }
+    }
+    
+    #[inline(always)]
+    unsafe fn get_unchecked(buf: &UnthBufRef<'_, Self>, index: usize) -> usize {
+        let loc = Self::location_of(buf, index);
+        
+        let mut value = 0;
+        for plane in 0..buf.bits.get() as usize {
+            let cell = *buf.data.get_unchecked(loc.cell + plane * loc.stride);
+            value |= ((cell >> loc.offset) & 1) << plane;
+        }
+        value
+    }
+}
+
+/// A location of an element within an [`UnthBuf`] using the [`InterleavedLayout`].
+#[derive(Clone, Copy)]
+pub struct InterleavedLocation {
+    /// The cell of the lowest bit, i.e. within the first plane.
+    pub(crate) cell: usize,
+    /// The bit within each of the cells.
+    pub(crate) offset: u8,
+    /// The amount of cells per plane.
+    pub(crate) stride: usize,
+}
+
+impl ElementLocation for InterleavedLocation {
+    /// Returns the cell holding the lowest bit of the element, within the first plane.
+    #[inline(always)]
+    fn cell(&self) -> usize {
+        self.cell
+    }
+}
+
+impl core::fmt::Debug for InterleavedLocation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[#{} +{}n <<{}]", self.cell, self.stride, self.offset)
+    }
+}
+
+impl UnthBuf<InterleavedLayout> {
+    /// Returns the cells of the bit-plane holding bit `bit` of every element, or [`None`] if `bit` is not below the bit-size.
+    /// 
+    /// Bit `i % BITS_PER_CELL` of cell `i / BITS_PER_CELL` is the bit of element `i`; padding bits are cleared.
+    pub fn plane(&self, bit: u8) -> Option<&[usize]> {
+        if bit >= self.bits.get() {return None}
+        
+        let plane_cells = get_plane_cell_count(self.capacity);
+        let start = bit as usize * plane_cells;
+        Some(&self.data[start..start + plane_cells])
+    }
+    
+    /// Creates a new bit-plane buffer with the elements of the given aligned buffer.
+    pub fn from_aligned(src: &AlignedUnthBuf) -> Self {
+        convert_layout(src)
+    }
+    
+    /// Copies the elements into a new aligned buffer, with the same bit-size and capacity.
+    pub fn to_aligned(&self) -> AlignedUnthBuf {
+        convert_layout(self)
+    }
+}
+
+/// Returns the amount of cells a single bit-plane of `capacity` bits needs.
+#[inline(always)]
+pub(crate) fn get_plane_cell_count(capacity: usize) -> usize {
+    capacity.div_ceil(BITS_PER_CELL as usize)
+}
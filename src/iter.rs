@@ -1,10 +1,12 @@
 //! Iteration for [`UnthBuf`]
 use crate::{UnthBuf, CellLayout};
+#[cfg(feature = "prefetch")]
+use crate::ElementLocation;
 use std::borrow::Cow;
 
 impl<CL: CellLayout> UnthBuf<CL> {
     /// Returns an iterator that yields all elements contained in this buffer.
-    pub fn iter(&self) -> UnthBufIter<CL> {
+    pub fn iter(&self) -> UnthBufIter<'_, CL> {
         UnthBufIter {
             idx: 0,
             cap: self.capacity,
@@ -13,6 +15,120 @@ impl<CL: CellLayout> UnthBuf<CL> {
     }
 }
 
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns an iterator over all overlapping windows of `n` consecutive elements, like [`slice::windows`].
+    /// 
+    /// If `n` is larger than the capacity, the iterator yields nothing.
+    /// 
+    /// # Panic
+    /// - Panics if `n` is `0`.
+    pub fn windows(&self, n: usize) -> impl Iterator<Item = Vec<usize>> + '_ {
+        assert!(n != 0, "window size must be non-zero");
+        
+        (0..(self.capacity + 1).saturating_sub(n)).map(move |start| {
+            (start..start + n)
+                // This is safe, as the window lies within the range of valid indices.
+                .map(|index| unsafe {self.get_unchecked(index)})
+                .collect()
+        })
+    }
+    
+    /// Returns an iterator over chunks of `n` consecutive elements, starting at the end, like [`slice::rchunks`].
+    /// 
+    /// The last chunk yielded holds the first elements of the buffer, and is shorter if `n` does not divide the capacity.
+    /// 
+    /// # Panic
+    /// - Panics if `n` is `0`.
+    pub fn rchunks(&self, n: usize) -> impl Iterator<Item = Vec<usize>> + '_ {
+        assert!(n != 0, "chunk size must be non-zero");
+        
+        (0..self.capacity.div_ceil(n)).map(move |chunk| {
+            let end = self.capacity - chunk * n;
+            self.copy_range(end.saturating_sub(n)..end).collect()
+        })
+    }
+}
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns an iterator over the raw cells, yielding each as `(cell, valid_mask)`.
+    /// 
+    /// The mask marks the bits of the cell that belong to elements; the final cell,
+    /// and for the aligned layout every cell with intra-cell padding, get a partial mask.
+    /// This allows SWAR-style processing of cells without touching padding.
+    pub fn iter_cells(&self) -> impl ExactSizeIterator<Item = (usize, usize)> + '_ {
+        let view = self.view();
+        self.data.iter().enumerate().map(move |(index, &cell)| (cell, CL::get_cell_mask(&view, index)))
+    }
+}
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Calls `f` with the index and a mutable copy of every element, writing the copy back afterwards.
+    /// 
+    /// As packed elements cannot be borrowed directly, this gives iterate-and-mutate ergonomics via a local.
+    /// 
+    /// # Panic
+    /// - Panics if a written-back value does not fit; all prior elements are already written back.
+    pub fn for_each_mut<F: FnMut(usize, &mut usize)>(&mut self, mut f: F) {
+        for index in self.get_indices() {
+            // This is safe, as the index comes from the range of valid indices.
+            let mut element = unsafe {self.get_unchecked(index)};
+            f(index, &mut element);
+            
+            assert!(self.can_element_fit(element), "value 0x{element:X} at index {index} does not fit into {} bits", self.bits);
+            // This is safe, as the index comes from the range of valid indices, and the value fits.
+            unsafe {self.set_unchecked(index, element)};
+        }
+    }
+}
+
+/// Roughly how many cells ahead of the current element [`UnthBuf::iter_prefetched`] prefetches.
+#[cfg(feature = "prefetch")]
+const PREFETCH_DISTANCE: usize = 16;
+
+#[cfg(feature = "prefetch")]
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns an iterator that yields all elements contained in this buffer,
+    /// while hinting the CPU to prefetch the cells that are decoded next.
+    /// 
+    /// This is a best-effort performance aid for sequential scans over huge buffers;
+    /// on targets without a stable prefetch instruction it is just plain iteration.
+    pub fn iter_prefetched(&self) -> impl Iterator<Item = usize> + '_ {
+        let view = self.view();
+        // Roughly the amount of elements stored in `PREFETCH_DISTANCE` cells.
+        let ahead = PREFETCH_DISTANCE * crate::BITS_PER_CELL as usize / self.bits.get() as usize;
+        let mut current = usize::MAX;
+        
+        self.get_indices().map(move |index| {
+            let cell = CL::location_of(&view, index).cell();
+            if cell != current {
+                // Entered a new cell; prefetch the cell of the element that is decoded in a while.
+                current = cell;
+                let target = (index + ahead).min(self.capacity - 1);
+                if let Some(cell) = self.data.get(CL::location_of(&view, target).cell()) {
+                    prefetch(cell);
+                }
+            }
+            
+            // This is safe, as the index comes from the range of valid indices.
+            unsafe {self.get_unchecked(index)}
+        })
+    }
+}
+
+/// Hints the CPU to fetch the cache-line holding the given cell.
+#[cfg(feature = "prefetch")]
+#[inline(always)]
+pub(crate) fn prefetch(cell: &usize) {
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        // Prefetching is only a hint, and never faults.
+        core::arch::x86_64::_mm_prefetch(cell as *const usize as *const i8, core::arch::x86_64::_MM_HINT_T0);
+    }
+    
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = cell;
+}
+
 impl<'buf, CL: CellLayout + 'static> IntoIterator for &'buf UnthBuf<CL> {
     type Item = usize;
     type IntoIter = UnthBufIter<'buf, CL>;
@@ -42,6 +158,9 @@ impl<CL: CellLayout + 'static> IntoIterator for UnthBuf<CL> {
 }
 
 /// Iterator over an [`UnthBuf`]
+/// 
+/// Both borrowed and owned buffers are held in a [`Cow`];
+/// the branch this costs per element is free in practice, see the `bench_iter_owned` benchmark.
 pub struct UnthBufIter<'buf, CL: CellLayout + 'static> {
     /// The [`UnthBuf`] to iterate over.
     pub(crate) buf: Cow<'buf, UnthBuf<CL>>,
@@ -53,11 +172,20 @@ pub struct UnthBufIter<'buf, CL: CellLayout + 'static> {
     pub(crate) cap: usize
 }
 
+impl<'buf, CL: CellLayout + 'static> UnthBufIter<'buf, CL> {
+    /// Consumes the iterator, returning the buffer it iterates over; borrowed or owned, just as it was given.
+    /// 
+    /// This allows recovering an owned buffer after (partially) iterating it, without cloning it.
+    pub fn into_inner(self) -> Cow<'buf, UnthBuf<CL>> {
+        self.buf
+    }
+}
+
 impl<CL: CellLayout + 'static> core::iter::Iterator for UnthBufIter<'_, CL> {
     type Item = usize;
     
     fn next(&mut self) -> Option<Self::Item> {
-        if ! self.idx < self.cap {
+        if self.idx >= self.cap {
             return None;
         }
         
@@ -71,6 +199,17 @@ impl<CL: CellLayout + 'static> core::iter::Iterator for UnthBufIter<'_, CL> {
         
         Some(item)
     }
+    
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // Skip ahead directly, instead of decoding every skipped element.
+        self.idx = self.idx.saturating_add(n).min(self.cap);
+        self.next()
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.cap - self.idx;
+        (remaining, Some(remaining))
+    }
 }
 
 impl<CL: CellLayout + 'static> core::iter::ExactSizeIterator for UnthBufIter<'_, CL> {
@@ -2,16 +2,40 @@
 #![deny(missing_docs)]
 #![allow(clippy::missing_inline_in_public_items)]
 #![allow(clippy::missing_const_for_fn)]
-#![allow(clippy::integer_arithmetic)]
+#![allow(clippy::arithmetic_side_effects)]
 #![allow(clippy::integer_division)]
 #![allow(clippy::implicit_return)]
 
 mod iter;
 mod fmt;
+mod cmp;
+mod error;
+mod view;
+mod ops;
+mod checksum;
+mod encoding;
+mod arith;
+mod bitwise;
+mod bulk;
+mod shared;
+mod atomic;
+mod constant;
+mod stream;
+mod writer;
+mod sort;
+mod reduce;
+mod growable;
+mod cursor;
+mod native;
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(any(test, feature = "test-util"))]
+mod conformance;
 
 // cell layouts
 pub mod aligned;
 pub mod packed;
+pub mod interleaved;
 
 
 /// A [`UnthBuf`] using the [`aligned::AlignedLayout`].
@@ -20,7 +44,28 @@ pub type AlignedUnthBuf = UnthBuf<aligned::AlignedLayout>;
 /// A [`UnthBuf`] using the [`packed::PackedLayout`].
 pub type PackedUnthBuf = UnthBuf<packed::PackedLayout>;
 
+/// A [`UnthBuf`] using the [`packed::PackedLayoutMsb`].
+pub type PackedMsbUnthBuf = UnthBuf<packed::PackedLayoutMsb>;
+
+/// A [`UnthBuf`] using the [`interleaved::InterleavedLayout`].
+pub type InterleavedUnthBuf = UnthBuf<interleaved::InterleavedLayout>;
+
 pub use iter::UnthBufIter;
+pub use error::UnthBufError;
+pub use view::{UnthBufRef, UnthBufMut};
+pub use shared::SharedUnthBuf;
+pub use atomic::AtomicUnthBuf;
+pub use constant::UnthBufConst;
+pub use writer::UnthBufWriter;
+pub use growable::GrowableUnthBuf;
+pub use cursor::UnthBufCursor;
+pub use fmt::Separated;
+pub use native::NativeElement;
+pub use sort::COUNTING_SORT_MAX_BITS;
+pub use ops::convert_layout;
+pub use stream::{elements_in_bytes, bytes_for_elements};
+#[cfg(any(test, feature = "test-util"))]
+pub use conformance::check_cell_layout_conformance;
 
 mod tests;
 
@@ -35,6 +80,9 @@ pub type Bits = core::num::NonZeroU8;
 /// Note: If the given bit-size is `0`, the internal buffer won't be allocated, and all operations are no-ops.
 /// 
 /// Internally, the [`UnthBuf`] is a boxed slice of **cells**, each holding a set amount of elements.
+/// 
+/// Cells are always `usize`, so the raw layout depends on the pointer-width of the target;
+/// for a layout that is identical across platforms, use [`Self::to_u64_cells`] or [`Self::to_byte_stream`].
 #[derive(Clone)]
 pub struct UnthBuf<CL: CellLayout> {
     /// Capacity of the buffer.
@@ -52,17 +100,36 @@ pub struct UnthBuf<CL: CellLayout> {
     /// Elements per cell.
     /// 
     /// - When   aligned, this is an exact number.
-    /// - When unaligned, this number is inexact.
+    /// - When unaligned, this number is inexact and thus meaningless.
     pub(crate) elpc: u8,
     
     /// Marker for cell layout.
     pub(crate) cell_layout: core::marker::PhantomData<CL>
 }
 
+/// The location of an element within the cells of an [`UnthBuf`], as calculated by [`CellLayout::location_of`].
+pub trait ElementLocation {
+    /// Returns the index of the cell holding the first bit of the element; e.g. for prefetching.
+    fn cell(&self) -> usize;
+}
+
 /// The internal layout of the cells held by an [`UnthBuf`].
+/// 
+/// Layouts can be implemented outside of this crate; the stable surface a layout may rely on is:
+/// - The accessors of [`UnthBufRef`] and [`UnthBufMut`], i.e. `len`, `get_element_bits`, `mask`, `elpc`, `data` and `data_mut`.
+/// - The public accessors of [`UnthBuf`], e.g. [`UnthBuf::raw`] and [`UnthBuf::get_element_bits`], for [`Self::get_exact_bit_count`].
+/// - Cells handed to a layout always number exactly [`Self::get_cell_count`], and start out zeroed.
+/// 
+/// In turn, a layout must never set padding bits; the `test-util` feature provides
+/// `check_cell_layout_conformance` to check this and the rest of the contract.
 pub trait CellLayout: Sized + Clone + Copy {
     /// Type representing an elements location.
-    type Location;
+    type Location: ElementLocation;
+    
+    /// Does this layout keep every element within a single cell?
+    /// 
+    /// If `true`, every cell holds exactly [`UnthBuf::elements_per_cell`] elements.
+    const ALIGNED: bool;
     
     /// Returns the amount of [`usize`]-cells needed to fit the given `capacity` × `bits` in.
     fn get_cell_count(capacity: usize, bits: Bits) -> usize;
@@ -70,22 +137,72 @@ pub trait CellLayout: Sized + Clone + Copy {
     /// Returns the exact amount of bits that are stored, excluding any padding.
     fn get_exact_bit_count(buf: &UnthBuf<Self>) -> usize;
     
+    /// Returns the mask of bits within the given cell that belong to elements, i.e. that are *not* padding.
+    /// 
+    /// The cell is not required to be valid for this operation.
+    fn get_cell_mask(buf: &UnthBufRef<'_, Self>, cell: usize) -> usize;
+    
     /// Calculates the exact location of the given index.
     /// 
     /// The index is not required to be valid for this operation.
-    fn location_of(buf: &UnthBuf<Self>, index: usize) -> Self::Location;
+    fn location_of(buf: &UnthBufRef<'_, Self>, index: usize) -> Self::Location;
     
-    /// Stores the given value at the given UNCHECKED index in the buffer.
+    /// Stores the given value at the given UNCHECKED index in the (borrowed) buffer.
     /// 
     /// # Safety
-    /// This function is safe if the provided index was tested with [`UnthBuf::is_index`]
-    unsafe fn set_unchecked(buf: &mut UnthBuf<Self>, index: usize, value: usize);
+    /// This function is safe if the provided index was tested with [`UnthBufMut::is_index`]
+    unsafe fn set_unchecked(buf: &mut UnthBufMut<'_, Self>, index: usize, value: usize);
     
-    /// Retrieves the value at the given UNCHECKED index from the buffer.
+    /// Retrieves the value at the given UNCHECKED index from the (borrowed) buffer.
     /// 
     /// # Safety
-    /// This function is safe if the provided index was tested with [`UnthBuf::is_index`]
-    unsafe fn get_unchecked(buf: &UnthBuf<Self>, index: usize) -> usize;
+    /// This function is safe if the provided index was tested with [`UnthBufRef::is_index`]
+    unsafe fn get_unchecked(buf: &UnthBufRef<'_, Self>, index: usize) -> usize;
+    
+    /// Decodes the leading elements of the (borrowed) buffer into the given slice, returning how many were written.
+    /// 
+    /// The default implementation decodes element by element; layouts can override it with a faster, cell-wise decoder.
+    fn decode_into(buf: &UnthBufRef<'_, Self>, out: &mut [usize]) -> usize {
+        let count = out.len().min(buf.capacity);
+        for (index, slot) in out[..count].iter_mut().enumerate() {
+            // This is safe, as the index is within the capacity.
+            *slot = unsafe {Self::get_unchecked(buf, index)};
+        }
+        count
+    }
+    
+    /// Stores the given value, which must fit, into every element of the (borrowed) buffer.
+    /// 
+    /// The default implementation stores the value element by element;
+    /// layouts can override it with a faster, cell-wise fill that leaves padding cleared.
+    fn fill(buf: &mut UnthBufMut<'_, Self>, value: usize) {
+        for index in 0..buf.capacity {
+            // This is safe, as the index comes from the range of valid indices.
+            unsafe {Self::set_unchecked(buf, index, value)};
+        }
+    }
+    
+    /// Stores the given pattern, whose values must fit, cyclically into the elements of the (borrowed) buffer.
+    /// 
+    /// The default implementation stores the pattern element by element;
+    /// layouts can override it with a faster, cell-wise tiling that leaves padding cleared.
+    fn fill_pattern(buf: &mut UnthBufMut<'_, Self>, pattern: &[usize]) {
+        buf.fill_from(pattern.iter().copied().cycle());
+    }
+    
+    /// Replaces every element `v` of the (borrowed) buffer with `v & mask`, where the mask fits.
+    /// 
+    /// The default implementation masks element by element;
+    /// layouts can override it with a faster, cell-wise masking that leaves padding cleared.
+    fn apply_mask(buf: &mut UnthBufMut<'_, Self>, mask: usize) {
+        for index in 0..buf.capacity {
+            // This is safe, as the index comes from the range of valid indices.
+            unsafe {
+                let element = Self::get_unchecked(&buf.view(), index);
+                Self::set_unchecked(buf, index, element & mask);
+            }
+        }
+    }
 }
 
 impl<CL: CellLayout> UnthBuf<CL> {
@@ -133,15 +250,97 @@ impl<CL: CellLayout> UnthBuf<CL> {
         new
     }
     
+    /// Creates a new [`UnthBuf`] with the given `capacity` and `bits`-size,
+    /// filling the element at every index `i` with `f(i)`, like [`core::array::from_fn`].
+    /// 
+    /// # Panic
+    /// - Panics if the given `capacity` is `0`.
+    /// - Panics if a produced value does not fit in `bits`, naming the offending index.
+    pub fn new_from_fn<F: FnMut(usize) -> usize>(bits: Bits, capacity: usize, mut f: F) -> Self {
+        let mut new = Self::new(bits, capacity);
+        for index in 0..capacity {
+            let value = f(index);
+            assert!(new.can_element_fit(value), "value 0x{value:X} produced for index {index} does not fit into {bits} bits");
+            // This is safe, as the index comes from the range of valid indices, and the value fits.
+            unsafe {new.set_unchecked(index, value)};
+        }
+        new
+    }
+    
+    /// Creates a new [`UnthBuf`] with the given `capacity` and `bits`-size, filled by cycling through the given `pattern`.
+    /// 
+    /// The [`aligned::AlignedLayout`] tiles whole cells at once, if the pattern length divides the elements per cell.
+    /// 
+    /// # Errors
+    /// - If the given `capacity` is `0`.
+    /// - If the `bits`-size is larger than a cell, or the total amount of bits overflows an [`usize`].
+    /// - If the pattern is empty.
+    /// - If any pattern value does not fit, reporting its index within the pattern.
+    pub fn from_pattern(bits: Bits, capacity: usize, pattern: &[usize]) -> Result<Self, UnthBufError> {
+        Self::checked_cell_count(bits, capacity)?;
+        if pattern.is_empty() {return Err(UnthBufError::EmptyPattern)}
+        
+        let mut new = Self::new(bits, capacity);
+        if let Some(index) = pattern.iter().position(|&value| !new.can_element_fit(value)) {
+            return Err(UnthBufError::ValueDoesNotFit { index, value: pattern[index] })
+        }
+        
+        CL::fill_pattern(&mut new.view_mut(), pattern);
+        Ok(new)
+    }
+    
     /// Creates a new [`UnthBuf`] with the given `capacity` and `bits`-size, filled with `0`.
     /// 
     /// # Panic
     /// - Panics if the given `capacity` is `0`.
+    /// - Panics if the `bits`-size is larger than a cell, or the total amount of bits overflows an [`usize`].
     pub fn new(bits: Bits, capacity: usize) -> Self {
-        assert!(capacity != 0, "cannot create buffer of 0 capacity");
-        
-        let size = CL::get_cell_count(capacity, bits);
+        let size = Self::checked_cell_count(bits, capacity).unwrap_or_else(|err| panic!("{err}"));
         let data = vec![0; size].into_boxed_slice();
+        Self::from_parts(bits, capacity, data)
+    }
+    
+    /// Creates a new [`UnthBuf`] with the given `capacity` and `bits`-size, taking ownership of the given cells.
+    /// 
+    /// The cells must already be laid out according to the [`CellLayout`]; they are *not* re-encoded.
+    /// 
+    /// # Errors
+    /// - If the given `capacity` is `0`.
+    /// - If the `bits`-size is larger than a cell.
+    /// - If the total amount of bits, `capacity * bits`, overflows an [`usize`].
+    /// - If the amount of cells does not match [`CellLayout::get_cell_count`].
+    pub fn from_raw_parts(bits: Bits, capacity: usize, data: Box<[usize]>) -> Result<Self, UnthBufError> {
+        let expected = Self::checked_cell_count(bits, capacity)?;
+        if data.len() != expected {
+            return Err(UnthBufError::CellCountMismatch { expected, actual: data.len() })
+        }
+        
+        Ok(Self::from_parts(bits, capacity, data))
+    }
+    
+    /// Decomposes this [`UnthBuf`] into its bit-size, capacity and cells, without copying them.
+    /// 
+    /// The parts can be turned back into an identical buffer via [`Self::from_raw_parts`].
+    pub fn into_raw_parts(self) -> (Bits, usize, Box<[usize]>) {
+        (self.bits, self.capacity, self.data)
+    }
+    
+    /// Returns the amount of cells the layout requires for the given `capacity` and `bits`-size, after validating both.
+    /// 
+    /// # Errors
+    /// - If the given `capacity` is `0`.
+    /// - If the `bits`-size is larger than a cell.
+    /// - If the total amount of bits, `capacity * bits`, overflows an [`usize`].
+    pub(crate) fn checked_cell_count(bits: Bits, capacity: usize) -> Result<usize, UnthBufError> {
+        if capacity == 0 {return Err(UnthBufError::ZeroCapacity)}
+        if bits.get() > BITS_PER_CELL {return Err(UnthBufError::InvalidBits { bits: bits.get() })}
+        if capacity.checked_mul(bits.get() as usize).is_none() {return Err(UnthBufError::CapacityOverflow { capacity })}
+        
+        Ok(CL::get_cell_count(capacity, bits))
+    }
+    
+    /// Assembles a [`UnthBuf`] from its parts, deriving the mask and elements-per-cell.
+    fn from_parts(bits: Bits, capacity: usize, data: Box<[usize]>) -> Self {
         let mask = Self::mask_from_bits(bits.get());
         let elpc = BITS_PER_CELL.checked_div(bits.get()).unwrap_or(0);
         
@@ -155,13 +354,28 @@ impl<CL: CellLayout> UnthBuf<CL> {
         }
     }
     
-    /// Gets the capacity of this buffer / how many elements are stored within.
+    /// Gets how many elements are stored within.
+    /// 
+    /// Unlike [`Vec::capacity`], there is no spare room; every element is in use.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.capacity
+    }
+    
+    /// Always `false`, as a buffer cannot be of 0 capacity.
     #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    
+    /// Gets the capacity of this buffer / how many elements are stored within; an alias of [`Self::len`].
+    #[inline(always)]
+    #[deprecated(note = "use `len` instead; there is no spare capacity, unlike `Vec::capacity`")]
     pub fn get_capacity(&self) -> usize {
         self.capacity
     }
     
-    /// Gets a range of all valid indices of this buffer; the same as `0 .. get_capacity()`.
+    /// Gets a range of all valid indices of this buffer; the same as `0 .. len()`.
     #[inline(always)]
     pub fn get_indices(&self) -> core::ops::Range<usize> {
         0..self.capacity
@@ -202,6 +416,45 @@ impl<CL: CellLayout> UnthBuf<CL> {
         &mut self.data
     }
     
+    /// Returns the cell at the given index of the backing buffer.
+    /// 
+    /// Out-of-bounds access, beyond [`Self::raw_len`], will return [`Option::None`].
+    #[inline]
+    pub fn cell(&self, cell: usize) -> Option<usize> {
+        self.data.get(cell).copied()
+    }
+    
+    /// Returns a mutable reference to the cell at the given index of the backing buffer.
+    /// 
+    /// Writing cells directly can set padding bits, which [`Self::clear_padding`] removes again.
+    /// 
+    /// Out-of-bounds access, beyond [`Self::raw_len`], will return [`Option::None`].
+    #[inline]
+    pub fn cell_mut(&mut self, cell: usize) -> Option<&mut usize> {
+        self.data.get_mut(cell)
+    }
+    
+    /// Returns the raw backing buffer of cells as bytes.
+    /// 
+    /// The bytes of each cell are in *native* endianness; this is meant for same-machine interop (e.g. FFI),
+    /// not for portable storage.
+    #[inline(always)]
+    pub fn as_byte_slice(&self) -> &[u8] {
+        // This is safe, as any bit-pattern of cells is valid as bytes, and `u8` has no alignment requirements.
+        unsafe {core::slice::from_raw_parts(self.data.as_ptr().cast::<u8>(), self.raw_byte_len())}
+    }
+    
+    /// Returns the raw backing buffer of cells as mutable bytes.
+    /// 
+    /// The bytes of each cell are in *native* endianness; this is meant for same-machine interop (e.g. FFI),
+    /// not for portable storage.
+    #[inline(always)]
+    pub fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+        let len = self.raw_byte_len();
+        // This is safe, as any bit-pattern of bytes is valid as cells, and `u8` has no alignment requirements.
+        unsafe {core::slice::from_raw_parts_mut(self.data.as_mut_ptr().cast::<u8>(), len)}
+    }
+    
     /// Gets the length of the backing buffer, in cells.
     #[inline(always)]
     pub fn raw_len(&self) -> usize {
@@ -220,6 +473,14 @@ impl<CL: CellLayout> UnthBuf<CL> {
         (value & self.mask) == value
     }
     
+    /// Checks if the given value can be stored in this buffer; an alias of [`Self::can_element_fit`].
+    #[inline(always)]
+    #[doc(hidden)]
+    #[deprecated(note = "use `can_element_fit` instead")]
+    pub fn fits(&self, value: usize) -> bool {
+        self.can_element_fit(value)
+    }
+    
     /// Returns the bitmask that is used to check if elements can fit in this buffer; see [`Self::can_element_fit`].
     #[inline(always)]
     pub fn get_element_mask(&self) -> usize {
@@ -232,6 +493,20 @@ impl<CL: CellLayout> UnthBuf<CL> {
         self.bits
     }
     
+    /// Returns the exact amount of elements stored per cell, if the layout is aligned.
+    /// 
+    /// For unaligned layouts, elements may straddle cells, so this returns [`None`].
+    #[inline(always)]
+    pub fn elements_per_cell(&self) -> Option<u8> {
+        CL::ALIGNED.then_some(self.elpc)
+    }
+    
+    /// Does the layout of this buffer keep every element within a single cell?
+    #[inline(always)]
+    pub fn is_aligned_layout(&self) -> bool {
+        CL::ALIGNED
+    }
+    
     /// Is the given index (`0..self.capacity`) valid for this buffer?
     #[inline(always)]
     pub fn is_index(&self, index: usize) -> bool {
@@ -246,24 +521,48 @@ impl<CL: CellLayout> UnthBuf<CL> {
         cell < self.data.len()
     }
     
+    /// Checks the integrity of this buffer, which may be broken if it was built from foreign cells.
+    /// 
+    /// # Errors
+    /// - If the amount of cells does not match [`CellLayout::get_cell_count`].
+    /// - If any padding bits are set; see [`CellLayout::get_cell_mask`].
+    pub fn validate(&self) -> Result<(), UnthBufError> {
+        let expected = CL::get_cell_count(self.capacity, self.bits);
+        if self.data.len() != expected {
+            return Err(UnthBufError::CellCountMismatch { expected, actual: self.data.len() })
+        }
+        
+        let view = self.view();
+        for (cell, bits) in self.data.iter().enumerate() {
+            if bits & !CL::get_cell_mask(&view, cell) != 0 {
+                return Err(UnthBufError::DirtyPadding { cell })
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Zeroes all padding bits in the backing buffer, leaving the elements untouched.
+    /// 
+    /// Stale padding can linger after editing cells via [`Self::raw_mut`];
+    /// clearing it makes comparing or compressing the raw cells deterministic.
+    pub fn clear_padding(&mut self) {
+        for cell in 0..self.data.len() {
+            let mask = CL::get_cell_mask(&self.view(), cell);
+            self.data[cell] &= mask;
+        }
+    }
+    
     /// Returns the location of the element at the given `index`.
     pub fn location_of(&self, index: usize) -> CL::Location {
-        CL::location_of(self, index)
+        CL::location_of(&self.view(), index)
     }
     
     /// Fills the buffer with the given value.
     /// 
     /// Filling with `0` is a memset, which is *very* fast.
     pub fn fill_with(&mut self, value: usize) {
-        assert!(self.can_element_fit(value), "given value does not fit");
-        
-        if value == 0 {
-            return self.fill_with_default()
-        }
-        
-        for index in self.get_indices() {
-            unsafe {self.set_unchecked(index, value)};
-        }
+        self.view_mut().fill_with(value);
     }
     
     /// Fills the buffer with `0`, clearing everything.
@@ -273,11 +572,25 @@ impl<CL: CellLayout> UnthBuf<CL> {
         self.data.fill(0);
     }
     
+    /// Fills the buffer with the largest value that fits, i.e. sets all bits of every element.
+    /// 
+    /// This is the counterpart to [`Self::fill_with_default`]; padding is left cleared.
+    pub fn fill_with_max(&mut self) {
+        self.fill_with(self.mask);
+    }
+    
     /// Fills the buffer with as many values from the given iterator as possible.
+    /// 
+    /// The values are expected to fit into the bit-size, but are not checked; values that do not fit corrupt neighbouring elements.
+    /// Use [`Self::fill_from_truncated`] to cut them down instead.
     pub fn fill_from(&mut self, iter: impl Iterator<Item = usize>) {
-        for (index, value) in self.get_indices().zip(iter).fuse() {
-            unsafe {self.set_unchecked(index, value)};
-        }
+        self.view_mut().fill_from(iter);
+    }
+    
+    /// Fills the buffer with as many values from the given iterator as possible,
+    /// truncating every value to the bit-size instead of spilling into neighbouring elements.
+    pub fn fill_from_truncated(&mut self, iter: impl Iterator<Item = usize>) {
+        self.view_mut().fill_from_truncated(iter);
     }
     
     /// Tries to set the element at the given `index` to the provided `value`.
@@ -293,13 +606,59 @@ impl<CL: CellLayout> UnthBuf<CL> {
         Ok(())
     }
     
+    /// Sets the element at the given `index` to the provided `value`, returning the previous element.
+    /// 
+    /// # Errors
+    /// - If the index is out of bounds; check with [`Self::is_index`].
+    /// - If the value does not fit; check with [`Self::can_element_fit`].
+    pub fn replace(&mut self, index: usize, value: usize) -> Result<usize, UnthBufError> {
+        self.check_element(index, value)?;
+        
+        // This is safe, as the index was checked above.
+        unsafe {
+            let previous = self.get_unchecked(index);
+            self.set_unchecked(index, value);
+            Ok(previous)
+        }
+    }
+    
+    /// Sets the element at the given `index` to `new`, but only if it currently equals `expected`.
+    /// 
+    /// Returns whether the element was set.
+    /// 
+    /// # Errors
+    /// - If the index is out of bounds; check with [`Self::is_index`].
+    /// - If `expected` or `new` does not fit; check with [`Self::can_element_fit`].
+    pub fn compare_and_set(&mut self, index: usize, expected: usize, new: usize) -> Result<bool, UnthBufError> {
+        self.check_element(index, expected)?;
+        self.check_element(index, new)?;
+        
+        // This is safe, as the index was checked above.
+        unsafe {
+            if self.get_unchecked(index) != expected {return Ok(false)}
+            self.set_unchecked(index, new);
+        }
+        Ok(true)
+    }
+    
+    /// Checks that the given index is in bounds and the given value fits.
+    pub(crate) fn check_element(&self, index: usize, value: usize) -> Result<(), UnthBufError> {
+        if !self.is_index(index) {
+            return Err(UnthBufError::RangeOutOfBounds { start: index, end: index.saturating_add(1), capacity: self.capacity })
+        }
+        if !self.can_element_fit(value) {return Err(UnthBufError::ValueDoesNotFit { index, value })}
+        Ok(())
+    }
+    
     /// Set the element at the given `index` to the provided `value`, *without* checking bounds.
     /// 
+    /// Debug builds assert that the index is in bounds and the value fits.
+    /// 
     /// # Safety
     /// If the index is not within `0..self.capacity`, testable via [`Self::is_index`], this function will cause undefined behaviour.
     #[inline(always)]
     pub unsafe fn set_unchecked(&mut self, index: usize, value: usize) {
-        CL::set_unchecked(self, index, value);
+        self.view_mut().set_unchecked(index, value);
     }
     
     /// Returns the element at the given `index`.
@@ -311,13 +670,46 @@ impl<CL: CellLayout> UnthBuf<CL> {
         Some(unsafe {self.get_unchecked(index)})
     }
     
+    /// Returns the element at the given `index`, or `default` if the index is out of bounds.
+    /// 
+    /// The `default` is returned as-is; it is *not* checked to fit.
+    #[inline]
+    pub fn get_or(&self, index: usize, default: usize) -> usize {
+        self.get(index).unwrap_or(default)
+    }
+    
+    /// Returns the first element.
+    /// 
+    /// As a buffer cannot be of 0 capacity, this always returns [`Option::Some`]; the signature mirrors slices.
+    #[inline]
+    pub fn first(&self) -> Option<usize> {
+        // This is safe, as the capacity is never 0.
+        Some(unsafe {self.get_unchecked(0)})
+    }
+    
+    /// Returns the last element.
+    /// 
+    /// As a buffer cannot be of 0 capacity, this always returns [`Option::Some`]; the signature mirrors slices.
+    #[inline]
+    pub fn last(&self) -> Option<usize> {
+        // This is safe, as the capacity is never 0.
+        Some(unsafe {self.get_unchecked(self.capacity - 1)})
+    }
+    
     /// Returns the element at the given `index`, *without* checking bounds.
     /// 
     /// # Safety
     /// If the index is not within `0..self.capacity`, this function will cause undefined behaviour.
     #[inline(always)]
     pub unsafe fn get_unchecked(&self, index: usize) -> usize {
-        CL::get_unchecked(self, index)
+        CL::get_unchecked(&self.view(), index)
+    }
+    
+    /// Returns the smallest bit-size that can hold the given value; at least `1`.
+    pub(crate) fn bits_for_value(value: usize) -> Bits {
+        let bits = (usize::BITS - value.leading_zeros()).max(1) as u8;
+        // This is safe, as the bit-count was clamped to at least 1.
+        unsafe {Bits::new_unchecked(bits)}
     }
     
     /// Returns a LSB-first bitmask of the given bit-length.
@@ -0,0 +1,47 @@
+//! Zero-copy typed views of [`AlignedUnthBuf`]s whose elements are primitive integers.
+use crate::{AlignedUnthBuf, BITS_PER_CELL};
+
+/// An unsigned primitive integer that the elements of an [`AlignedUnthBuf`] can be viewed as; see [`AlignedUnthBuf::try_as_native_slice`].
+/// 
+/// This trait is sealed, and implemented for `u8`, `u16`, `u32` and `u64`.
+pub trait NativeElement: Copy + sealed::Sealed {
+    /// The size of the integer, in bits.
+    const BITS: u32;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! native_element {
+    ($($ty:ty),*) => {$(
+        impl sealed::Sealed for $ty {}
+        impl NativeElement for $ty {
+            const BITS: u32 = <$ty>::BITS;
+        }
+    )*};
+}
+
+native_element!(u8, u16, u32, u64);
+
+impl AlignedUnthBuf {
+    /// Returns the elements as a slice of `T`, without copying, if the cells are laid out exactly like one.
+    /// 
+    /// This is only available for the [`crate::aligned::AlignedLayout`], as other layouts may order the elements differently,
+    /// and returns [`Option::Some`] only if *all* of the following hold:
+    /// - The bit-size equals the size of `T`, and is at most the size of a cell.
+    /// - The target is little-endian, so the first element of a cell is also the first `T` in memory.
+    /// 
+    /// Cells are always aligned at least as strictly as `T`, and hold enough `T`s, so no other conditions apply.
+    pub fn try_as_native_slice<T: NativeElement>(&self) -> Option<&[T]> {
+        let compatible = self.bits.get() as u32 == T::BITS
+            && T::BITS <= BITS_PER_CELL as u32
+            && cfg!(target_endian = "little");
+        
+        if !compatible {return None}
+        
+        // This is safe, as every cell consists of exactly `elpc` elements of `T`, in order;
+        // the cells are aligned for `T`, and hold at least `capacity` elements.
+        Some(unsafe {core::slice::from_raw_parts(self.data.as_ptr().cast::<T>(), self.capacity)})
+    }
+}
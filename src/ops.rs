@@ -0,0 +1,158 @@
+//! Structural operations on [`UnthBuf`]
+use crate::{UnthBuf, UnthBufError, CellLayout, Bits};
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Splits the buffer into two new buffers at the given element index,
+    /// holding the elements `[0..mid)` and `[mid..capacity)` respectively.
+    /// 
+    /// Since element boundaries generally don't align with cells, the elements are copied one by one.
+    /// 
+    /// # Panic
+    /// - Panics if `mid` is `0` or not smaller than the capacity, as either half would be of 0 capacity.
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid != 0 && mid < self.capacity, "cannot split buffer of capacity {} at {mid}", self.capacity);
+        
+        let head = Self::new_from_sized_iter(self.bits, self.copy_range(0..mid));
+        let tail = Self::new_from_sized_iter(self.bits, self.copy_range(mid..self.capacity));
+        (head, tail)
+    }
+    
+    /// Creates a new buffer holding the elements of `self`, followed by the elements of `other`.
+    /// 
+    /// Since cell boundaries generally don't align, the elements are copied one by one.
+    /// 
+    /// # Errors
+    /// - If the bit-sizes of both buffers do not match.
+    pub fn concat(&self, other: &UnthBuf<CL>) -> Result<Self, UnthBufError> {
+        if self.bits != other.bits {
+            return Err(UnthBufError::BitsMismatch { expected: self.bits, actual: other.bits })
+        }
+        
+        Ok(Self::new_from_capacity_and_iter(
+            self.bits,
+            self.capacity + other.capacity,
+            self.copy_range(self.get_indices()).chain(other.copy_range(other.get_indices()))
+        ))
+    }
+    
+    /// Creates a new buffer holding a copy of the elements within the given `range`.
+    /// 
+    /// # Errors
+    /// - If the range is not within `0..self.capacity`.
+    /// - If the range is empty, as the new buffer would be of 0 capacity.
+    pub fn subrange(&self, range: core::ops::Range<usize>) -> Result<Self, UnthBufError> {
+        if range.end > self.capacity {
+            return Err(UnthBufError::RangeOutOfBounds { start: range.start, end: range.end, capacity: self.capacity })
+        }
+        if range.is_empty() {return Err(UnthBufError::ZeroCapacity)}
+        
+        Ok(Self::new_from_sized_iter(self.bits, self.copy_range(range)))
+    }
+    
+    /// Creates a new buffer with the given capacity, holding the elements of this buffer;
+    /// truncated if the new capacity is smaller, or extended with `fill` if it is larger.
+    /// 
+    /// # Errors
+    /// - If `new_capacity` is `0`.
+    /// - If the total amount of bits, `new_capacity * bits`, overflows an [`usize`].
+    /// - If `fill` does not fit, even if the buffer is only truncated.
+    pub fn resize(&self, new_capacity: usize, fill: usize) -> Result<Self, UnthBufError> {
+        Self::checked_cell_count(self.bits, new_capacity)?;
+        if !self.can_element_fit(fill) {
+            return Err(UnthBufError::ValueDoesNotFit { index: self.capacity, value: fill })
+        }
+        
+        let kept = self.capacity.min(new_capacity);
+        let elements = self.copy_range(0..kept).chain(core::iter::repeat(fill));
+        Ok(Self::new_from_capacity_and_iter(self.bits, new_capacity, elements))
+    }
+    
+    /// Creates a new buffer holding the first `len` elements of this buffer; see [`Self::resize`].
+    /// 
+    /// If `len` is not smaller than the capacity, this is a plain copy.
+    /// 
+    /// # Errors
+    /// - If `len` is `0`.
+    pub fn truncate(&self, len: usize) -> Result<Self, UnthBufError> {
+        self.resize(len.min(self.capacity), 0)
+    }
+    
+    /// Creates a new buffer holding the elements of this buffer, followed by `additional` times `fill`; see [`Self::resize`].
+    /// 
+    /// # Errors
+    /// - If the new capacity, or its total amount of bits, overflows an [`usize`].
+    /// - If `fill` does not fit.
+    pub fn grow(&self, additional: usize, fill: usize) -> Result<Self, UnthBufError> {
+        let capacity = self.capacity.checked_add(additional).ok_or(UnthBufError::CapacityOverflow { capacity: usize::MAX })?;
+        self.resize(capacity, fill)
+    }
+    
+    /// Changes the bit-size of this buffer to `new_bits`, widening or narrowing it while keeping all elements.
+    /// 
+    /// This usually reallocates the backing buffer, but allows call-sites that cannot move the buffer to change it.
+    /// 
+    /// # Errors
+    /// - If narrowing, and any element does not fit into `new_bits`, reporting the first one; the buffer is left unchanged.
+    pub fn rebit_in_place(&mut self, new_bits: Bits) -> Result<(), UnthBufError> {
+        if new_bits == self.bits {return Ok(())}
+        
+        if new_bits < self.bits {
+            let mask = Self::mask_from_bits(new_bits.get());
+            if let Some((index, value)) = self.copy_range(self.get_indices()).enumerate().find(|&(_, value)| value & mask != value) {
+                return Err(UnthBufError::ValueDoesNotFit { index, value })
+            }
+        }
+        
+        *self = Self::new_from_sized_iter(new_bits, self.copy_range(self.get_indices()));
+        Ok(())
+    }
+    
+    /// Creates a new buffer where every run of equal adjacent elements is collapsed into one, preserving order.
+    /// 
+    /// The new buffer holds at least one element, as the capacity is never 0.
+    pub fn dedup(&self) -> Self {
+        let mut elements: Vec<usize> = self.copy_range(self.get_indices()).collect();
+        elements.dedup();
+        Self::new_from_sized_iter(self.bits, elements.into_iter())
+    }
+    
+    /// Creates a new buffer holding only the elements for which `f` returns `true`, preserving order,
+    /// together with the original indices of the kept elements.
+    /// 
+    /// The indices allow keeping a parallel array in sync with the filtered buffer.
+    /// 
+    /// # Errors
+    /// - If no element is kept, as the buffer would be of 0 capacity.
+    pub fn filter_with_indices<F: FnMut(usize) -> bool>(&self, mut f: F) -> Result<(Self, Vec<usize>), UnthBufError> {
+        let (indices, elements): (Vec<usize>, Vec<usize>) = self.copy_range(self.get_indices())
+            .enumerate()
+            .filter(|&(_, value)| f(value))
+            .unzip();
+        
+        if elements.is_empty() {return Err(UnthBufError::ZeroCapacity)}
+        Ok((Self::new_from_sized_iter(self.bits, elements.into_iter()), indices))
+    }
+    
+    /// Copies the elements into a new buffer of layout `D`, with the same bit-size and capacity; see [`convert_layout`].
+    /// 
+    /// To build a buffer of a chosen layout and bit-size from arbitrary elements instead,
+    /// use [`UnthBuf::new_from_sized_iter`] (or [`UnthBuf::new_from_capacity_and_iter`] if the length is unknown);
+    /// collecting via [`FromIterator`] picks the smallest fitting bit-size.
+    pub fn recollect<D: CellLayout>(&self) -> UnthBuf<D> {
+        convert_layout(self)
+    }
+    
+    /// Returns an iterator over the elements within the given range.
+    /// 
+    /// The range must be within `0..self.capacity`.
+    pub(crate) fn copy_range(&self, range: core::ops::Range<usize>) -> impl ExactSizeIterator<Item = usize> + '_ {
+        debug_assert!(range.end <= self.capacity, "range {range:?} is out of bounds");
+        // This is safe, as long as the range is within bounds.
+        range.map(|index| unsafe {self.get_unchecked(index)})
+    }
+}
+
+/// Copies the elements of the given buffer into a new buffer of layout `B`, with the same bit-size and capacity.
+pub fn convert_layout<A: CellLayout, B: CellLayout>(src: &UnthBuf<A>) -> UnthBuf<B> {
+    UnthBuf::new_from_sized_iter(src.bits, src.copy_range(src.get_indices()))
+}
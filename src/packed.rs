@@ -1,5 +1,5 @@
 //! Layout that stores integers tightly packed, *across* word boundaries.
-use crate::{UnthBuf, CellLayout, Bits, BITS_PER_CELL};
+use crate::{UnthBuf, UnthBufRef, UnthBufMut, CellLayout, ElementLocation, Bits, BITS_PER_CELL, convert_layout};
 
 /// Layout that stores integers tightly packed, *across* word boundaries.
 #[derive(Clone, Copy)]
@@ -8,17 +8,49 @@ pub struct PackedLayout;
 impl CellLayout for PackedLayout {
     type Location = PackedLocation;
     
+    const ALIGNED: bool = false;
+    
     #[inline(always)]
     fn get_cell_count(capacity: usize, bits: Bits) -> usize {
-        (capacity * bits.get() as usize) / 8
+        (capacity * bits.get() as usize).div_ceil(BITS_PER_CELL as usize)
     }
     
     fn get_exact_bit_count(buf: &UnthBuf<Self>) -> usize {
         buf.capacity * buf.bits.get() as usize
     }
     
+    fn get_cell_mask(buf: &UnthBufRef<'_, Self>, cell: usize) -> usize {
+        let first = cell.saturating_mul(BITS_PER_CELL as usize);
+        let bits = (buf.capacity * buf.bits.get() as usize).saturating_sub(first).min(BITS_PER_CELL as usize);
+        UnthBuf::<Self>::mask_from_bits(bits as u8)
+    }
+    
+    fn fill(buf: &mut UnthBufMut<'_, Self>, value: usize) {
+        // Elements realign with cell boundaries every `bits / gcd(bits, BITS_PER_CELL)` cells;
+        // one such period of cells is built element by element, then repeated.
+        let bits = buf.bits.get() as usize;
+        let period = bits >> (bits.trailing_zeros().min(BITS_PER_CELL.trailing_zeros()));
+        let mut pattern = vec![0usize; period];
+        
+        for element in 0..(period * BITS_PER_CELL as usize / bits) {
+            let bitindex = element * bits;
+            let cell = get_packed_cellindex_low(bitindex);
+            let offset = get_packed_element_offset_low(bitindex) as usize;
+            
+            pattern[cell] |= value << offset;
+            if offset + bits > BITS_PER_CELL as usize {
+                pattern[cell + 1] |= value >> (BITS_PER_CELL as usize - offset);
+            }
+        }
+        
+        for cell in 0..buf.data.len() {
+            let mask = Self::get_cell_mask(&buf.view(), cell);
+            buf.data[cell] = pattern[cell % period] & mask;
+        }
+    }
+    
     #[inline(always)]
-    fn location_of(buf: &UnthBuf<Self>, index: usize) -> Self::Location {
+    fn location_of(buf: &UnthBufRef<'_, Self>, index: usize) -> Self::Location {
         //if !self.is_index(index) {eprintln!("index {index} is out of bounds; {:?}", self)}
         let bitindex = get_packed_bitindex(index, buf.bits.get());
         let cell = get_packed_cellindex_low(bitindex);
@@ -38,8 +70,8 @@ impl CellLayout for PackedLayout {
     }
 
     #[inline(always)]
-    unsafe fn set_unchecked(buf: &mut UnthBuf<Self>, index: usize, value: usize) {
-        let location = Self::location_of(buf, index);
+    unsafe fn set_unchecked(buf: &mut UnthBufMut<'_, Self>, index: usize, value: usize) {
+        let location = Self::location_of(&buf.view(), index);
         
         if location.mask0 != 0 {
             let mut lcell = *buf.data.get_unchecked(location.cell);
@@ -50,18 +82,21 @@ impl CellLayout for PackedLayout {
             *buf.data.get_unchecked_mut(location.cell) = lcell;
         }
         
+        // Full-width elements always start at offset 0 and never reach into the high cell;
+        // thus `mask1` is only set when `0 < offset1 < bits`, and the shift below is never full-width.
+        // The shift is guarded regardless, just like in `get_packed_element_mask_high`.
         if location.mask1 != 0 {
             let mut hcell = *buf.data.get_unchecked(location.cell + 1);
             
             hcell &= !location.mask1; // unset the bits of the old value
-            hcell |= value >> (buf.bits.get() - location.offset1); // set bits for new value
+            hcell |= value.overflowing_shr((buf.bits.get() - location.offset1) as u32).0; // set bits for new value
             
             *buf.data.get_unchecked_mut(location.cell + 1) = hcell;
         }
     }
 
     #[inline(always)]
-    unsafe fn get_unchecked(buf: &UnthBuf<Self>, index: usize) -> usize {
+    unsafe fn get_unchecked(buf: &UnthBufRef<'_, Self>, index: usize) -> usize {
         let location = Self::location_of(buf, index);
         
         //if !buf.is_cell(loc.cell) {panic!("unaligned cell @{index} -> {:?} out of bounds; {:?}", loc, self)}
@@ -75,7 +110,7 @@ impl CellLayout for PackedLayout {
             let mut high = *buf.data.get_unchecked(location.cell + 1);
             
             high &= location.mask1;
-            high <<= buf.bits.get() - location.offset1;
+            high = high.overflowing_shl((buf.bits.get() - location.offset1) as u32).0;
             low |= high;
         }
         
@@ -93,12 +128,138 @@ pub struct PackedLocation {
     pub(crate) mask1: usize,
 }
 
+impl ElementLocation for PackedLocation {
+    #[inline(always)]
+    fn cell(&self) -> usize {
+        self.cell
+    }
+}
+
 impl core::fmt::Debug for PackedLocation {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "[#{} <<h{}l{} &h{:b}l{:b}]", self.cell, self.offset1, self.offset0, self.mask1, self.mask0)
     }
 }
 
+/// Layout that stores integers tightly packed *across* word boundaries, like [`PackedLayout`], but MSB-first.
+/// 
+/// The first element occupies the *highest* bits of the first cell, with its most significant bit first;
+/// an element straddling two cells keeps its high bits in the lower bits of the first cell,
+/// and its low bits in the upper bits of the next cell. This matches formats that pack big-endian words MSB-first.
+#[derive(Clone, Copy)]
+pub struct PackedLayoutMsb;
+
+impl CellLayout for PackedLayoutMsb {
+    type Location = PackedMsbLocation;
+    
+    const ALIGNED: bool = false;
+    
+    #[inline(always)]
+    fn get_cell_count(capacity: usize, bits: Bits) -> usize {
+        PackedLayout::get_cell_count(capacity, bits)
+    }
+    
+    fn get_exact_bit_count(buf: &UnthBuf<Self>) -> usize {
+        buf.capacity * buf.bits.get() as usize
+    }
+    
+    fn get_cell_mask(buf: &UnthBufRef<'_, Self>, cell: usize) -> usize {
+        let first = cell.saturating_mul(BITS_PER_CELL as usize);
+        let bits = (buf.capacity * buf.bits.get() as usize).saturating_sub(first).min(BITS_PER_CELL as usize);
+        UnthBuf::<Self>::mask_from_bits(bits as u8)
+            .checked_shl(BITS_PER_CELL as u32 - bits as u32)
+            .unwrap_or(0)
+    }
+    
+    #[inline(always)]
+    fn location_of(buf: &UnthBufRef<'_, Self>, index: usize) -> Self::Location {
+        let bitindex = get_packed_bitindex(index, buf.bits.get());
+        PackedMsbLocation {
+            cell: get_packed_cellindex_low(bitindex),
+            offset: get_packed_element_offset_low(bitindex),
+            straddles: get_packed_element_offset_low(bitindex) + buf.bits.get() > BITS_PER_CELL,
+        }
+    }
+    
+    #[inline(always)]
+    unsafe fn set_unchecked(buf: &mut UnthBufMut<'_, Self>, index: usize, value: usize) {
+        let location = Self::location_of(&buf.view(), index);
+        let shift = location.window_shift(buf.bits.get());
+        
+        let mut window = get_msb_window(buf.data, location);
+        window &= !((buf.mask as u128) << shift); // unset the bits of the old value
+        window |= (value as u128) << shift; // set bits for new value
+        
+        *buf.data.get_unchecked_mut(location.cell) = (window >> BITS_PER_CELL) as usize;
+        if location.straddles {
+            *buf.data.get_unchecked_mut(location.cell + 1) = window as usize;
+        }
+    }
+    
+    #[inline(always)]
+    unsafe fn get_unchecked(buf: &UnthBufRef<'_, Self>, index: usize) -> usize {
+        let location = Self::location_of(buf, index);
+        let shift = location.window_shift(buf.bits.get());
+        
+        (get_msb_window(buf.data, location) >> shift) as usize & buf.mask
+    }
+}
+
+/// A location of an element within an [`UnthBuf`] using the [`PackedLayoutMsb`].
+#[derive(Clone, Copy)]
+pub struct PackedMsbLocation {
+    /// The cell holding the most significant bit of the element.
+    pub(crate) cell: usize,
+    /// The offset of the most significant bit of the element, counted from the top of the cell.
+    pub(crate) offset: u8,
+    /// Does the element reach into the next cell?
+    pub(crate) straddles: bool,
+}
+
+impl PackedMsbLocation {
+    /// Returns the shift of an element of the given bit-size within the window of two cells, see [`get_msb_window`].
+    #[inline(always)]
+    fn window_shift(&self, bits: u8) -> u32 {
+        2 * BITS_PER_CELL as u32 - self.offset as u32 - bits as u32
+    }
+}
+
+impl ElementLocation for PackedMsbLocation {
+    #[inline(always)]
+    fn cell(&self) -> usize {
+        self.cell
+    }
+}
+
+impl core::fmt::Debug for PackedMsbLocation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[#{} >>{}{}]", self.cell, self.offset, if self.straddles {" +1"} else {""})
+    }
+}
+
+impl UnthBuf<PackedLayoutMsb> {
+    /// Creates a new MSB-first buffer with the elements of the given LSB-first buffer.
+    pub fn from_lsb(src: &UnthBuf<PackedLayout>) -> Self {
+        convert_layout(src)
+    }
+    
+    /// Copies the elements into a new LSB-first buffer, with the same bit-size and capacity.
+    pub fn to_lsb(&self) -> UnthBuf<PackedLayout> {
+        convert_layout(self)
+    }
+}
+
+/// Returns the cell of the given location in the upper half and, if the element straddles, the next cell in the lower half.
+/// 
+/// # Safety
+/// The location must belong to a valid index of the cells.
+#[inline(always)]
+unsafe fn get_msb_window(data: &[usize], location: PackedMsbLocation) -> u128 {
+    let high = *data.get_unchecked(location.cell) as u128;
+    let low = if location.straddles {*data.get_unchecked(location.cell + 1) as u128} else {0};
+    high << BITS_PER_CELL | low
+}
+
 #[inline(always)]
 pub(crate) fn get_packed_bitindex(index: usize, bits: u8) -> usize {
     index * bits as usize
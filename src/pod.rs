@@ -0,0 +1,24 @@
+//! Zero-copy casting of the cells of an [`UnthBuf`], via [`bytemuck`].
+use crate::{UnthBuf, UnthBufError, CellLayout, Bits};
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns the raw backing buffer of cells as bytes, using [`bytemuck::cast_slice`].
+    /// 
+    /// The bytes of each cell are in *native* endianness; this is meant for same-machine interop,
+    /// not for portable storage.
+    pub fn cast_cells(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.data)
+    }
+    
+    /// Creates a new [`UnthBuf`] with the given `bits`-size and `capacity`, copying its cells from the given bytes.
+    /// 
+    /// The bytes must hold the cells in *native* endianness, as returned by [`Self::cast_cells`].
+    /// 
+    /// # Errors
+    /// - If the bytes are not aligned to, or not a whole multiple of, the size of a cell.
+    /// - Any error of [`Self::from_raw_parts`], e.g. if the amount of cells does not match [`CellLayout::get_cell_count`].
+    pub fn from_cell_bytes(bits: Bits, capacity: usize, bytes: &[u8]) -> Result<Self, UnthBufError> {
+        let cells: &[usize] = bytemuck::try_cast_slice(bytes).map_err(|_| UnthBufError::MisalignedBytes)?;
+        Self::from_raw_parts(bits, capacity, cells.into())
+    }
+}
@@ -0,0 +1,97 @@
+//! Reductions over the elements of [`UnthBuf`]
+use crate::{UnthBuf, UnthBufError, CellLayout, Bits};
+use core::ops::ControlFlow;
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns the smallest bit-size that can hold every element of this buffer; at least `1`.
+    /// 
+    /// This scans all elements, and is thus `O(n)`.
+    pub fn required_bits(&self) -> Bits {
+        let max = self.get_indices()
+            // This is safe, as the index comes from the range of valid indices.
+            .map(|index| unsafe {self.get_unchecked(index)})
+            .max()
+            .unwrap_or(0);
+        Self::bits_for_value(max)
+    }
+    
+    /// Returns a histogram of how many significant bits the elements need:
+    /// index `k` counts the elements whose value needs exactly `k` bits, with `0` needing none.
+    /// 
+    /// The array is fixed-size, covering every bit-size up to 64; indices above the bit-size of this buffer are always `0`.
+    /// This scans all elements, and is thus `O(n)`; e.g. for estimating the gain of delta- or entropy-coding.
+    pub fn significant_bits_histogram(&self) -> [usize; 65] {
+        self.fold([0; 65], |mut histogram, element| {
+            histogram[(usize::BITS - element.leading_zeros()) as usize] += 1;
+            histogram
+        })
+    }
+    
+    /// Folds every element into an accumulator, in order.
+    pub fn fold<B, F: FnMut(B, usize) -> B>(&self, init: B, f: F) -> B {
+        self.copy_range(self.get_indices()).fold(init, f)
+    }
+    
+    /// Folds every element into an accumulator, in order, stopping as soon as `f` returns [`ControlFlow::Break`].
+    /// 
+    /// Returns [`ControlFlow::Break`] with the accumulator at the point of stopping,
+    /// or [`ControlFlow::Continue`] with the final accumulator if all elements were folded.
+    pub fn try_fold<B, F: FnMut(B, usize) -> ControlFlow<B, B>>(&self, init: B, mut f: F) -> ControlFlow<B, B> {
+        let mut acc = init;
+        for element in self.copy_range(self.get_indices()) {
+            acc = f(acc, element)?;
+        }
+        ControlFlow::Continue(acc)
+    }
+    
+    /// Returns the bitwise AND of all elements, starting from the element mask.
+    /// 
+    /// As a buffer cannot be of 0 capacity, there is always at least one element.
+    pub fn reduce_and(&self) -> usize {
+        self.fold(self.mask, |acc, element| acc & element)
+    }
+    
+    /// Returns the bitwise OR of all elements; non-zero if any bit is set anywhere.
+    /// 
+    /// As a buffer cannot be of 0 capacity, there is always at least one element.
+    pub fn reduce_or(&self) -> usize {
+        self.fold(0, |acc, element| acc | element)
+    }
+    
+    /// Returns the sum of `element_i * weights_i` over all elements, widened to `u128`.
+    /// 
+    /// Every product fits into a `u128`; the sum only wraps around once it exceeds `u128::MAX`.
+    /// 
+    /// # Errors
+    /// - If the amount of weights does not match the capacity.
+    pub fn dot(&self, weights: &[usize]) -> Result<u128, UnthBufError> {
+        if weights.len() != self.capacity {
+            return Err(UnthBufError::LengthMismatch { expected: self.capacity, actual: weights.len() })
+        }
+        
+        Ok(self.copy_range(self.get_indices())
+            .zip(weights)
+            .fold(0u128, |acc, (element, &weight)| acc.wrapping_add(element as u128 * weight as u128)))
+    }
+    
+    /// Returns the index of the smallest element; the first one, if several are equally small.
+    pub fn argmin(&self) -> usize {
+        self.arg_by(|candidate, best| candidate < best)
+    }
+    
+    /// Returns the index of the largest element; the first one, if several are equally large.
+    pub fn argmax(&self) -> usize {
+        self.arg_by(|candidate, best| candidate > best)
+    }
+    
+    /// Returns the index of the first element that no later element `replaces`.
+    fn arg_by(&self, replaces: impl Fn(usize, usize) -> bool) -> usize {
+        let mut best = (0, self.first().unwrap_or(0));
+        for (index, element) in self.copy_range(1..self.capacity).enumerate() {
+            if replaces(element, best.1) {
+                best = (index + 1, element);
+            }
+        }
+        best.0
+    }
+}
@@ -0,0 +1,60 @@
+//! Shared immutable [`UnthBuf`]
+use crate::{UnthBuf, UnthBufRef, UnthBufIter, CellLayout};
+use std::sync::Arc;
+
+/// An immutable [`UnthBuf`] that can be cheaply cloned and shared across threads, without copying its cells.
+#[derive(Clone)]
+pub struct SharedUnthBuf<CL: CellLayout> {
+    /// The shared buffer.
+    pub(crate) inner: Arc<UnthBuf<CL>>,
+}
+
+impl<CL: CellLayout> SharedUnthBuf<CL> {
+    /// Creates a new [`SharedUnthBuf`], taking ownership of the given buffer.
+    pub fn new(buf: UnthBuf<CL>) -> Self {
+        Self { inner: Arc::new(buf) }
+    }
+    
+    /// Gets how many elements are stored within.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.inner.capacity
+    }
+    
+    /// Always `false`, as a buffer cannot be of 0 capacity.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    
+    /// Returns the element at the given `index`.
+    /// 
+    /// Out-of-bounds access will return [`Option::None`].
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<usize> {
+        self.inner.get(index)
+    }
+    
+    /// Returns an iterator that yields all elements contained in this buffer.
+    pub fn iter(&self) -> UnthBufIter<'_, CL> {
+        self.inner.iter()
+    }
+    
+    /// Returns a read-only [`UnthBufRef`] view of this buffer.
+    #[inline(always)]
+    pub fn view(&self) -> UnthBufRef<'_, CL> {
+        self.inner.view()
+    }
+}
+
+impl<CL: CellLayout> From<UnthBuf<CL>> for SharedUnthBuf<CL> {
+    fn from(buf: UnthBuf<CL>) -> Self {
+        Self::new(buf)
+    }
+}
+
+impl<CL: CellLayout> core::fmt::Debug for SharedUnthBuf<CL> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
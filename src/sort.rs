@@ -0,0 +1,78 @@
+//! Sorting and searching of [`UnthBuf`]
+use crate::{UnthBuf, CellLayout};
+use core::cmp::Ordering;
+
+/// The largest bit-size for which [`UnthBuf::sort_in_place`] uses a counting sort.
+pub const COUNTING_SORT_MAX_BITS: u8 = 16;
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Checks if the elements of this buffer are sorted in ascending order.
+    pub fn is_sorted(&self) -> bool {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+    
+    /// Checks if the elements of this buffer are sorted, using the given function to check every adjacent pair.
+    /// 
+    /// Like [`slice::is_sorted_by`], the function must return `true` if the pair is in order.
+    pub fn is_sorted_by<F: FnMut(usize, usize) -> bool>(&self, mut cmp: F) -> bool {
+        (1..self.capacity).all(|index| unsafe {
+            // This is safe, as both indices are within the range of valid indices.
+            cmp(self.get_unchecked(index - 1), self.get_unchecked(index))
+        })
+    }
+    
+    /// Searches this buffer for the given value, like [`slice::binary_search`].
+    /// 
+    /// The buffer must be sorted in ascending order, otherwise the result is unspecified.
+    /// 
+    /// Returns [`Ok`] with the index of a matching element (any one, if there are several),
+    /// or [`Err`] with the index where the value could be inserted while keeping the order.
+    pub fn binary_search(&self, value: usize) -> Result<usize, usize> {
+        let mut low = 0;
+        let mut high = self.capacity;
+        
+        while low < high {
+            let mid = low + (high - low) / 2;
+            // This is safe, as `mid` is always below `high`, which is at most the capacity.
+            match unsafe {self.get_unchecked(mid)}.cmp(&value) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid)
+            }
+        }
+        
+        Err(low)
+    }
+    
+    /// Returns a copy of this buffer, with its elements sorted in ascending order.
+    /// 
+    /// See [`Self::sort_in_place`].
+    pub fn sorted(&self) -> Self {
+        let mut sorted = self.clone();
+        sorted.sort_in_place();
+        sorted
+    }
+    
+    /// Sorts the elements of this buffer in ascending order.
+    /// 
+    /// For bit-sizes up to [`COUNTING_SORT_MAX_BITS`], this is a counting sort in `O(n + 2^bits)`;
+    /// otherwise, the elements are decoded into a temporary [`Vec`], sorted via [`slice::sort_unstable`], and written back.
+    pub fn sort_in_place(&mut self) {
+        if self.bits.get() <= COUNTING_SORT_MAX_BITS {
+            let mut histogram = vec![0usize; 1 << self.bits.get()];
+            for index in self.get_indices() {
+                // This is safe, as the index comes from the range of valid indices.
+                histogram[unsafe {self.get_unchecked(index)}] += 1;
+            }
+            
+            let elements = histogram.into_iter().enumerate().flat_map(|(value, count)| core::iter::repeat_n(value, count));
+            self.fill_from(elements);
+            return;
+        }
+        
+        let mut elements = vec![0; self.capacity];
+        self.decode_into(&mut elements);
+        elements.sort_unstable();
+        self.fill_from(elements.into_iter());
+    }
+}
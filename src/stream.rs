@@ -0,0 +1,192 @@
+//! Tightly packed byte streams of [`UnthBuf`] elements.
+use crate::{UnthBuf, UnthBufError, CellLayout, Bits, BITS_PER_CELL};
+
+/// The length of the header written by [`UnthBuf::to_be_bytes`].
+const BE_HEADER_LEN: usize = 10;
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Creates a new [`UnthBuf`] by reading `capacity` elements of `bits` bits each from a tightly packed byte stream.
+    /// 
+    /// The elements are read LSB-first, i.e. the first element starts at the lowest bit of the first byte.
+    /// 
+    /// # Errors
+    /// - If the given `capacity` is `0`.
+    /// - If the total amount of bits, `capacity * bits`, overflows an [`usize`].
+    /// - If the stream is shorter than `ceil(capacity * bits / 8)` bytes.
+    pub fn from_byte_stream(bits: Bits, capacity: usize, bytes: &[u8]) -> Result<Self, UnthBufError> {
+        if capacity == 0 {return Err(UnthBufError::ZeroCapacity)}
+        
+        let needed = bytes_for_elements(bits, capacity).ok_or(UnthBufError::CapacityOverflow { capacity })?;
+        if bytes.len() < needed {
+            return Err(UnthBufError::LengthMismatch { expected: needed, actual: bytes.len() })
+        }
+        
+        let mask = Self::mask_from_bits(bits.get()) as u128;
+        let mut bytes = bytes.iter();
+        let mut pending = 0u128;
+        let mut pending_bits = 0;
+        
+        let elements = core::iter::from_fn(|| {
+            while pending_bits < bits.get() {
+                // The length check above guarantees enough bytes.
+                pending |= (*bytes.next()? as u128) << pending_bits;
+                pending_bits += 8;
+            }
+            
+            let element = (pending & mask) as usize;
+            pending >>= bits.get();
+            pending_bits -= bits.get();
+            Some(element)
+        });
+        
+        Ok(Self::new_from_capacity_and_iter(bits, capacity, elements))
+    }
+    
+    /// Writes all elements into a tightly packed byte stream of exactly `ceil(capacity * bits / 8)` bytes.
+    /// 
+    /// The elements are written LSB-first, i.e. the first element starts at the lowest bit of the first byte.
+    /// The output is independent of the [`CellLayout`] and pointer-width, making it the portable form of a buffer;
+    /// it can be read back via [`Self::from_byte_stream`].
+    pub fn to_byte_stream(&self) -> Vec<u8> {
+        // The bits of an existing buffer never overflow, so this is always `Some`.
+        let mut bytes = Vec::with_capacity(bytes_for_elements(self.bits, self.capacity).unwrap_or(0));
+        let mut pending = 0u128;
+        let mut pending_bits = 0;
+        
+        for index in self.get_indices() {
+            // This is safe, as the index comes from the range of valid indices.
+            pending |= (unsafe {self.get_unchecked(index)} as u128) << pending_bits;
+            pending_bits += self.bits.get();
+            
+            while pending_bits >= 8 {
+                bytes.push(pending as u8);
+                pending >>= 8;
+                pending_bits -= 8;
+            }
+        }
+        
+        if pending_bits > 0 {
+            bytes.push(pending as u8);
+        }
+        
+        bytes
+    }
+    
+    /// Writes all elements into a tightly packed stream of 64-bit cells, regardless of the native cell width.
+    /// 
+    /// This is the bit stream of [`Self::to_byte_stream`], grouped into little-endian `u64`s; on 64-bit targets it
+    /// matches the native cells of the packed layout, while 32-bit targets get the same result by re-packing.
+    /// It can be read back via [`Self::from_u64_cells`].
+    pub fn to_u64_cells(&self) -> Vec<u64> {
+        self.to_byte_stream()
+            .chunks(8)
+            .map(|chunk| {
+                let mut word = [0u8; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(word)
+            })
+            .collect()
+    }
+    
+    /// Creates a new [`UnthBuf`] by reading `capacity` elements of `bits` bits each from a tightly packed stream of 64-bit cells,
+    /// as written by [`Self::to_u64_cells`].
+    /// 
+    /// # Errors
+    /// - If the given `capacity` is `0`.
+    /// - If the total amount of bits, `capacity * bits`, overflows an [`usize`].
+    /// - If there are fewer than `ceil(capacity * bits / 64)` cells.
+    pub fn from_u64_cells(bits: Bits, capacity: usize, cells: &[u64]) -> Result<Self, UnthBufError> {
+        let needed = capacity.checked_mul(bits.get() as usize)
+            .ok_or(UnthBufError::CapacityOverflow { capacity })?
+            .div_ceil(64);
+        if cells.len() < needed {
+            return Err(UnthBufError::LengthMismatch { expected: needed, actual: cells.len() })
+        }
+        
+        let bytes: Vec<u8> = cells.iter().flat_map(|cell| cell.to_le_bytes()).collect();
+        Self::from_byte_stream(bits, capacity, &bytes)
+    }
+    
+    /// Writes a header followed by all cells as big-endian bytes, independent of the endianness of the host.
+    /// 
+    /// The header is the `bits`-size (1 byte), the size of a cell in bytes (1 byte) and the capacity (8 bytes, big-endian).
+    /// The cells keep the [`CellLayout`], so the buffer must be read back via [`Self::from_be_bytes`] with the same layout.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(BE_HEADER_LEN + self.raw_byte_len());
+        bytes.push(self.bits.get());
+        bytes.push(size_of::<usize>() as u8);
+        bytes.extend((self.capacity as u64).to_be_bytes());
+        bytes.extend(self.data.iter().flat_map(|cell| cell.to_be_bytes()));
+        bytes
+    }
+    
+    /// Creates a new [`UnthBuf`] from a header and big-endian cells, as written by [`Self::to_be_bytes`].
+    /// 
+    /// # Errors
+    /// - If the header is truncated, or its cell size differs from the cells of this target.
+    /// - If the `bits`-size is `0` or larger than a cell.
+    /// - If the capacity is `0`, or more than the cells can hold.
+    /// - If the bytes after the header are not a whole multiple of the size of a cell,
+    ///   or not the amount of cells the layout requires.
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<Self, UnthBufError> {
+        let Some((header, cells)) = bytes.split_first_chunk::<BE_HEADER_LEN>() else {
+            return Err(UnthBufError::LengthMismatch { expected: BE_HEADER_LEN, actual: bytes.len() })
+        };
+        
+        let cell_size = size_of::<usize>();
+        if header[1] as usize != cell_size {
+            return Err(UnthBufError::LengthMismatch { expected: cell_size, actual: header[1] as usize })
+        }
+        
+        let bits = match Bits::new(header[0]) {
+            Some(bits) if bits.get() <= BITS_PER_CELL => bits,
+            _ => return Err(UnthBufError::InvalidBits { bits: header[0] })
+        };
+        
+        if !cells.len().is_multiple_of(cell_size) {return Err(UnthBufError::MisalignedBytes)}
+        
+        // Reject capacities the cells cannot possibly hold first, so computing the required cell count cannot overflow.
+        let cell_count = cells.len() / cell_size;
+        let max_capacity = cell_count * BITS_PER_CELL as usize / bits.get() as usize;
+        let capacity = u64::from_be_bytes(header[2..].try_into().unwrap_or_default());
+        if capacity > max_capacity as u64 {
+            return Err(UnthBufError::LengthMismatch { expected: max_capacity, actual: usize::try_from(capacity).unwrap_or(usize::MAX) })
+        }
+        
+        let cells = cells.chunks_exact(cell_size)
+            .map(|chunk| usize::from_be_bytes(chunk.try_into().unwrap_or_default()))
+            .collect();
+        Self::from_raw_parts(bits, capacity as usize, cells)
+    }
+    
+    /// Writes all cells as little-endian bytes, zero-padded to a multiple of `alignment` bytes; e.g. for GPU upload.
+    /// 
+    /// Unlike [`Self::to_byte_stream`] this keeps the [`CellLayout`], so shaders can decode elements like the CPU does;
+    /// with 64-bit cells, a shader reading 32-bit words finds cell `c` in words `2c` (low half) and `2c + 1` (high half).
+    /// For std140/std430 storage buffers, an `alignment` of `16` is sufficient.
+    /// 
+    /// # Panic
+    /// - Panics if `alignment` is not a power of two.
+    pub fn to_gpu_bytes(&self, alignment: usize) -> Vec<u8> {
+        assert!(alignment.is_power_of_two(), "alignment {alignment} is not a power of two");
+        
+        let mut bytes: Vec<u8> = self.data.iter().flat_map(|cell| cell.to_le_bytes()).collect();
+        bytes.resize(bytes.len().next_multiple_of(alignment), 0);
+        bytes
+    }
+}
+
+/// Returns how many elements of the given `bits`-size fit into `byte_budget` bytes, when tightly packed.
+/// 
+/// This matches the packed layout and [`UnthBuf::to_byte_stream`]; the aligned layout may fit fewer, due to padding.
+pub fn elements_in_bytes(bits: Bits, byte_budget: usize) -> usize {
+    byte_budget.saturating_mul(8) / bits.get() as usize
+}
+
+/// Returns how many bytes `count` tightly packed elements of the given `bits`-size occupy; the inverse of [`elements_in_bytes`].
+/// 
+/// This is exactly the length of [`UnthBuf::to_byte_stream`];
+/// returns [`None`] if the total amount of bits, `count * bits`, overflows an [`usize`].
+pub fn bytes_for_elements(bits: Bits, count: usize) -> Option<usize> {
+    count.checked_mul(bits.get() as usize).map(|total| total.div_ceil(8))
+}
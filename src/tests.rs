@@ -1,7 +1,7 @@
 //! Modules containing tests.
 
 #[cfg(test)]
-use crate::{*, aligned::*, packed::*};
+use crate::{*, aligned::*, packed::*, interleaved::*};
 
 #[cfg(test)]
 const PRIMES: &[usize] = &[2, 5, 13, 29, 61, 113, 251, 509, 1021, 2039, 4093, 8179, 16381, 32749, 65521, 131063, 262139, 524269, 1048573, 2097143, 4194301, 8388593, 16777213, 33554393, 67108859, 134217689, 268435399, 536870909, 1073741789, 2147483629, 4294967291, 8589934583, 17179869143, 34359738337, 68719476731, 137438953447, 274877906899, 549755813881, 1099511627689, 2199023255531, 4398046511093, 8796093022151, 17592186044399, 35184372088777, 70368744177643, 140737488355213, 281474976710597, 562949953421231, 1125899906842597, 2251799813685119, 4503599627370449, 9007199254740881, 18014398509481951, 36028797018963913, 72057594037927931, 144115188075855859, 288230376151711717, 576460752303423433, 1152921504606846883, 2305843009213693921, 4611686018427387847, 9223372036854775783, 18446744073709551557];
@@ -14,7 +14,7 @@ fn aligned_location_of() {
         println!("--- {bits} BITS");
         
         for loc in buf.get_indices() {
-            let loc = buf.location_of(loc as usize);
+            let loc = buf.location_of(loc);
             print!("{loc:?} ")
         }
         println!();
@@ -29,13 +29,36 @@ fn packed_location_of() {
         println!("--- {bits} BITS");
         
         for loc in buf.get_indices() {
-            let loc = buf.location_of(loc as usize);
+            let loc = buf.location_of(loc);
             print!("{loc:?} ")
         }
         println!();
     }
 }
 
+#[test]
+fn iter_stops_at_capacity() {
+    let buf = AlignedUnthBuf::new_with_default(Bits::new(5).unwrap(), 10, 7);
+    let mut iter = buf.iter();
+    assert_eq!(iter.len(), 10);
+    assert!(iter.by_ref().take(10).all(|element| element == 7));
+    assert_eq!(iter.next(), None);
+    assert_eq!(buf.iter().count(), 10);
+}
+
+#[test]
+fn packed_cell_count() {
+    let bits = |b| Bits::new(b).unwrap();
+    assert_eq!(PackedUnthBuf::new(bits(2), 3).raw_len(), 1);
+    assert_eq!(PackedUnthBuf::new(bits(1), BITS_PER_CELL as usize).raw_len(), 1);
+    assert_eq!(PackedUnthBuf::new(bits(1), BITS_PER_CELL as usize + 1).raw_len(), 2);
+    assert_eq!(PackedUnthBuf::new(bits(BITS_PER_CELL), 1000).raw_len(), 1000);
+    
+    let mut buf = PackedUnthBuf::new(bits(2), 3);
+    buf.set(2, 3).unwrap();
+    assert_eq!(buf.get(2), Some(3));
+}
+
 #[test]
 fn aligned_setget() {
     
@@ -122,9 +145,8 @@ fn test_values(bitsize: u8, rng: &mut rand::prelude::StdRng) -> Vec<ValType> {
 }
 
 #[cfg(test)]
-fn test_indices(rng: &mut rand::prelude::StdRng) -> Vec<usize> {
-    use rand::prelude::*;
-    let mut indices: Vec<usize> = (0..ITERATIONS).collect();
+fn test_indices(_rng: &mut rand::prelude::StdRng) -> Vec<usize> {
+    let indices: Vec<usize> = (0..ITERATIONS).collect();
     //indices.shuffle(rng);
     indices
 }
@@ -154,7 +176,7 @@ pub fn bench_io_unaligned() {
     println!("Initia. {} unaligned values took {} ms / {} ns per int.", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
     
     // write bench
-    let values = test_values(bitsize.try_into().unwrap(), &mut rng);
+    let values = test_values(bitsize, &mut rng);
     let now = Instant::now();
     for i in indices.iter().copied() {
         packed.set(i, values[i] as usize).unwrap();
@@ -258,3 +280,1826 @@ pub fn bench_io_baseline() {
     let elapsed = now.elapsed();
     println!("Reading {}  baseline values took {} ms / {} ns per int.", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
 }
+
+#[test]
+fn compare_buffers() {
+    let bits4 = Bits::new(4).unwrap();
+    let bits5 = Bits::new(5).unwrap();
+    
+    let a = PackedUnthBuf::new_from_sized_iter(bits4, [1, 2, 3].into_iter());
+    let b = PackedUnthBuf::new_from_sized_iter(bits4, [1, 2, 4].into_iter());
+    let c = PackedUnthBuf::new_from_sized_iter(bits4, [1, 2, 3, 0].into_iter());
+    let d = PackedUnthBuf::new_from_sized_iter(bits5, [1, 2, 3].into_iter());
+    
+    assert_eq!(a, a.clone());
+    assert_ne!(a, d);
+    assert!(a < b);
+    assert!(a < c);
+    assert!(c < b);
+    assert!(a < d);
+    
+    let mut sorted = vec![b.clone(), d.clone(), c.clone(), a.clone()];
+    sorted.sort();
+    assert_eq!(sorted, vec![a, d, c, b]);
+}
+
+#[test]
+fn from_raw_parts() {
+    let bits = Bits::new(7).unwrap();
+    let buf = PackedUnthBuf::new_from_sized_iter(bits, (0..100).map(|i| i % 128));
+    
+    let copy = PackedUnthBuf::from_raw_parts(bits, 100, buf.raw().into()).unwrap();
+    assert_eq!(buf, copy);
+    
+    let (parts_bits, parts_capacity, parts_data) = copy.into_raw_parts();
+    let copy = PackedUnthBuf::from_raw_parts(parts_bits, parts_capacity, parts_data).unwrap();
+    assert_eq!(buf, copy);
+    assert_eq!(buf.raw(), copy.raw());
+    
+    let short = buf.raw()[1..].into();
+    assert_eq!(
+        PackedUnthBuf::from_raw_parts(bits, 100, short).unwrap_err(),
+        UnthBufError::CellCountMismatch { expected: buf.raw_len(), actual: buf.raw_len() - 1 }
+    );
+    
+    assert_eq!(
+        AlignedUnthBuf::from_raw_parts(bits, 0, Box::new([])).unwrap_err(),
+        UnthBufError::ZeroCapacity
+    );
+}
+
+#[test]
+fn from_raw_parts_rejects_overflow_and_invalid_bits() {
+    for b in [2, 7, BITS_PER_CELL] {
+        let bits = Bits::new(b).unwrap();
+        let capacity = usize::MAX / b as usize + 1;
+        assert_eq!(PackedUnthBuf::from_raw_parts(bits, capacity, vec![7].into()), Err(UnthBufError::CapacityOverflow { capacity }));
+        assert_eq!(AlignedUnthBuf::from_raw_parts(bits, capacity, vec![7].into()), Err(UnthBufError::CapacityOverflow { capacity }));
+    }
+    
+    // The largest capacity that does not overflow must not overflow while counting cells either.
+    let one = Bits::new(1).unwrap();
+    assert!(matches!(AlignedUnthBuf::from_raw_parts(one, usize::MAX, vec![7].into()), Err(UnthBufError::CellCountMismatch { .. })));
+    assert!(matches!(PackedUnthBuf::from_raw_parts(one, usize::MAX, vec![7].into()), Err(UnthBufError::CellCountMismatch { .. })));
+    
+    for b in [BITS_PER_CELL + 1, u8::MAX] {
+        let bits = Bits::new(b).unwrap();
+        assert_eq!(PackedUnthBuf::from_raw_parts(bits, 1, vec![7; 8].into()), Err(UnthBufError::InvalidBits { bits: b }));
+        assert_eq!(AlignedUnthBuf::from_raw_parts(bits, 1, vec![7; 8].into()), Err(UnthBufError::InvalidBits { bits: b }));
+    }
+}
+
+#[test]
+#[should_panic(expected = "overflow usize")]
+fn new_rejects_overflow() {
+    PackedUnthBuf::new(Bits::new(2).unwrap(), usize::MAX);
+}
+
+#[test]
+fn borrowed_view() {
+    let bits = Bits::new(5).unwrap();
+    let buf = AlignedUnthBuf::new_from_sized_iter(bits, (0..50).map(|i| i % 32));
+    
+    let view = UnthBufRef::<AlignedLayout>::new(bits, 50, buf.raw()).unwrap();
+    assert_eq!(view.len(), 50);
+    assert_eq!(view.get(49), Some(49 % 32));
+    assert_eq!(view.get(50), None);
+    assert!(view.iter().eq(buf.iter()));
+    
+    assert!(UnthBufRef::<AlignedLayout>::new(bits, 50, &buf.raw()[1..]).is_err());
+}
+
+#[test]
+fn borrowed_view_rejects_overflow_and_invalid_bits() {
+    let cells = [7usize; 8];
+    let bits = Bits::new(BITS_PER_CELL).unwrap();
+    let capacity = usize::MAX / BITS_PER_CELL as usize + 1;
+    assert_eq!(UnthBufRef::<PackedLayout>::new(bits, capacity, &cells[..1]).map(|v| v.len()), Err(UnthBufError::CapacityOverflow { capacity }));
+    assert_eq!(UnthBufRef::<AlignedLayout>::new(bits, capacity, &cells[..1]).map(|v| v.len()), Err(UnthBufError::CapacityOverflow { capacity }));
+    
+    let bits = Bits::new(BITS_PER_CELL + 1).unwrap();
+    assert_eq!(UnthBufRef::<PackedLayout>::new(bits, 1, &cells).map(|v| v.len()), Err(UnthBufError::InvalidBits { bits: BITS_PER_CELL + 1 }));
+}
+
+#[test]
+fn mutable_view() {
+    let bits = Bits::new(3).unwrap();
+    let cells = PackedLayout::get_cell_count(40, bits);
+    
+    // A chunk embedded in the middle of a larger buffer.
+    let mut world = vec![usize::MAX; cells + 2];
+    let mut view = UnthBufMut::<PackedLayout>::new(bits, 40, &mut world[1..=cells]).unwrap();
+    view.fill_with_default();
+    view.fill_from((0..40).map(|i| i % 8));
+    view.set(39, 5).unwrap();
+    assert!(view.set(40, 5).is_err());
+    assert!(view.set(0, 8).is_err());
+    assert_eq!(view.get(39), Some(5));
+    assert_eq!(view.get(21), Some(21 % 8));
+    
+    assert_eq!(world[0], usize::MAX);
+    assert_eq!(world[cells + 1], usize::MAX);
+}
+
+#[test]
+fn mutable_view_rejects_overflow_and_invalid_bits() {
+    let mut cells = [0usize; 8];
+    let bits = Bits::new(BITS_PER_CELL).unwrap();
+    let capacity = usize::MAX / BITS_PER_CELL as usize + 1;
+    assert_eq!(UnthBufMut::<PackedLayout>::new(bits, capacity, &mut cells[..1]).map(|v| v.len()), Err(UnthBufError::CapacityOverflow { capacity }));
+    assert_eq!(UnthBufMut::<AlignedLayout>::new(bits, capacity, &mut cells[..1]).map(|v| v.len()), Err(UnthBufError::CapacityOverflow { capacity }));
+    
+    let bits = Bits::new(BITS_PER_CELL + 1).unwrap();
+    assert_eq!(UnthBufMut::<PackedLayout>::new(bits, 1, &mut cells).map(|v| v.len()), Err(UnthBufError::InvalidBits { bits: BITS_PER_CELL + 1 }));
+}
+
+#[test]
+fn split_at() {
+    let bits = Bits::new(7).unwrap();
+    let buf = PackedUnthBuf::new_from_sized_iter(bits, (0..100).map(|i| i % 128));
+    
+    let (head, tail) = buf.split_at(37);
+    assert_eq!(head.len(), 37);
+    assert_eq!(tail.len(), 63);
+    assert!(head.iter().chain(tail.iter()).eq(buf.iter()));
+}
+
+#[test]
+#[should_panic]
+fn split_at_end() {
+    let buf = AlignedUnthBuf::new(Bits::new(7).unwrap(), 10);
+    let _ = buf.split_at(10);
+}
+
+#[test]
+fn concat() {
+    // 7 bits straddle the cell boundaries of the packed layout.
+    let bits = Bits::new(7).unwrap();
+    let a = PackedUnthBuf::new_from_sized_iter(bits, (0..19).map(|i| i * 3 % 128));
+    let b = PackedUnthBuf::new_from_sized_iter(bits, (0..23).map(|i| i * 5 % 128));
+    
+    let ab = a.concat(&b).unwrap();
+    assert_eq!(ab.len(), 42);
+    assert!(ab.iter().eq(a.iter().chain(b.iter())));
+    assert_eq!(ab.split_at(19), (a, b));
+    
+    let c = PackedUnthBuf::new(Bits::new(8).unwrap(), 3);
+    assert!(matches!(ab.concat(&c), Err(UnthBufError::BitsMismatch {..})));
+}
+
+#[test]
+fn subrange() {
+    let bits = Bits::new(6).unwrap();
+    let buf = AlignedUnthBuf::new_from_sized_iter(bits, (0..64).rev());
+    
+    let sub = buf.subrange(10..20).unwrap();
+    assert!(sub.iter().eq((44..54).rev()));
+    
+    assert_eq!(buf.subrange(5..5), Err(UnthBufError::ZeroCapacity));
+    assert!(matches!(buf.subrange(60..65), Err(UnthBufError::RangeOutOfBounds {..})));
+}
+
+#[test]
+fn format_hex() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(10).unwrap(), [0x3FF, 0xA, 0x1B2].into_iter());
+    assert_eq!(format!("{buf}"), "[u10; 3; 1023, 10, 434]");
+    assert_eq!(format!("{buf:x}"), "[u10; 3; 3ff, 00a, 1b2]");
+    assert_eq!(format!("{buf:X}"), "[u10; 3; 3FF, 00A, 1B2]");
+}
+
+#[test]
+fn format_binary() {
+    let mut buf = AlignedUnthBuf::new(Bits::new(5).unwrap(), 4);
+    buf.set(0, 0b10110).unwrap();
+    buf.set(2, 0b00001).unwrap();
+    buf.set(3, 0b11111).unwrap();
+    assert_eq!(format!("{buf:b}"), "[u5; 4; 0b10110, 0b00000, 0b00001, 0b11111]");
+}
+
+#[test]
+fn debug_cells() {
+    // The second element starts 4 bits below the end of the first cell, on any pointer-width.
+    let bits = BITS_PER_CELL - 4;
+    let mut buf = PackedUnthBuf::new(Bits::new(bits).unwrap(), 2);
+    buf.set(0, 0b101).unwrap();
+    buf.set(1, 0b111).unwrap();
+    
+    let dump = buf.debug_cells();
+    let lines: Vec<&str> = dump.lines().collect();
+    assert_eq!(lines.len(), buf.raw_len());
+    assert_eq!(lines[0], format!("#0: {:0w$b}", (0b111_usize << bits) | 0b101, w = usize::BITS as usize));
+    assert_eq!(lines[1], format!("#1: {:0w$b}", 0, w = usize::BITS as usize));
+}
+
+#[test]
+fn layout_introspection() {
+    let bits = Bits::new(5).unwrap();
+    let aligned = AlignedUnthBuf::new(bits, 100);
+    let packed = PackedUnthBuf::new(bits, 100);
+    
+    assert!(aligned.is_aligned_layout());
+    assert!(!packed.is_aligned_layout());
+    assert_eq!(aligned.elements_per_cell(), Some((usize::BITS / 5) as u8));
+    assert_eq!(packed.elements_per_cell(), None);
+}
+
+#[test]
+fn checksum() {
+    let bits = Bits::new(11).unwrap();
+    let aligned = AlignedUnthBuf::new_from_sized_iter(bits, (0..300).map(|i| i * 7 % 2048));
+    let packed = PackedUnthBuf::new_from_sized_iter(bits, (0..300).map(|i| i * 7 % 2048));
+    assert_eq!(aligned.checksum(), packed.checksum());
+    
+    let mut other = packed.clone();
+    other.set(123, 0).unwrap();
+    assert_ne!(packed.checksum(), other.checksum());
+    
+    // Garbage in the padding does not change the checksum.
+    let mut dirty = aligned.clone();
+    *dirty.raw_mut().last_mut().unwrap() |= 1 << (usize::BITS - 1);
+    assert_eq!(aligned.checksum(), dirty.checksum());
+}
+
+#[test]
+fn validate() {
+    let bits = Bits::new(5).unwrap();
+    let aligned = AlignedUnthBuf::new_with_default(bits, 30, 31);
+    let packed = PackedUnthBuf::new_with_default(bits, 30, 31);
+    assert_eq!(aligned.validate(), Ok(()));
+    assert_eq!(packed.validate(), Ok(()));
+    
+    // The topmost bits of an aligned cell are intra-cell padding.
+    let mut dirty = aligned.clone();
+    dirty.raw_mut()[0] |= 1 << (usize::BITS - 1);
+    assert_eq!(dirty.validate(), Err(UnthBufError::DirtyPadding { cell: 0 }));
+    
+    let mut dirty = packed.clone();
+    *dirty.raw_mut().last_mut().unwrap() |= 1 << (usize::BITS - 1);
+    assert_eq!(dirty.validate(), Err(UnthBufError::DirtyPadding { cell: packed.raw_len() - 1 }));
+}
+
+#[test]
+fn clear_padding() {
+    let bits = Bits::new(7).unwrap();
+    let aligned = AlignedUnthBuf::new_from_sized_iter(bits, (0..100).map(|i| i % 128));
+    let packed = PackedUnthBuf::new_from_sized_iter(bits, (0..100).map(|i| i % 128));
+    
+    let mut dirty = aligned.clone();
+    dirty.raw_mut().iter_mut().for_each(|cell| *cell |= 1 << (usize::BITS - 1));
+    assert!(dirty.validate().is_err());
+    dirty.clear_padding();
+    assert_eq!(dirty.validate(), Ok(()));
+    assert_eq!(dirty.raw(), aligned.raw());
+    
+    let mut dirty = packed.clone();
+    *dirty.raw_mut().last_mut().unwrap() |= 1 << (usize::BITS - 1);
+    assert!(dirty.validate().is_err());
+    dirty.clear_padding();
+    assert_eq!(dirty.validate(), Ok(()));
+    assert_eq!(dirty.raw(), packed.raw());
+}
+
+#[test]
+fn run_length_encoding() {
+    let bits = Bits::new(3).unwrap();
+    let runs = [(1, 4), (7, 1), (0, 10), (1, 3)];
+    
+    let buf = PackedUnthBuf::from_rle(bits, &runs).unwrap();
+    assert_eq!(buf.len(), 18);
+    assert_eq!(buf.to_rle(), runs);
+    
+    assert_eq!(PackedUnthBuf::from_rle(bits, &[(1, 0)]), Err(UnthBufError::ZeroCapacity));
+    assert_eq!(
+        PackedUnthBuf::from_rle(bits, &[(1, 2), (8, 1)]),
+        Err(UnthBufError::ValueDoesNotFit { index: 2, value: 8 })
+    );
+    
+    // Hostile run lengths must neither wrap around nor allocate.
+    assert_eq!(
+        PackedUnthBuf::from_rle(bits, &[(0, usize::MAX), (0, 2)]),
+        Err(UnthBufError::CapacityOverflow { capacity: usize::MAX })
+    );
+    assert_eq!(
+        PackedUnthBuf::from_rle(bits, &[(0, usize::MAX / 2), (1, 1)]),
+        Err(UnthBufError::CapacityOverflow { capacity: usize::MAX / 2 + 1 })
+    );
+}
+
+#[test]
+fn delta_encoding() {
+    let values = [1000, 1001, 1005, 1005, 1020, 1033];
+    let buf = AlignedUnthBuf::new_from_sized_iter(Bits::new(11).unwrap(), values.into_iter());
+    
+    let (first, deltas) = buf.to_deltas().unwrap();
+    assert_eq!(first, 1000);
+    assert_eq!(deltas.get_element_bits().get(), 4);
+    assert!(deltas.iter().eq([0, 1, 4, 0, 15, 13].into_iter()));
+    
+    let decoded = AlignedUnthBuf::from_deltas(buf.get_element_bits(), first, &deltas);
+    assert_eq!(decoded, buf);
+    assert_eq!(decoded.get_element_bits(), buf.get_element_bits());
+    
+    // A single element round-trips, with a lone difference of `0`.
+    let single = AlignedUnthBuf::new_from_sized_iter(Bits::new(11).unwrap(), [1234].into_iter());
+    let (first, deltas) = single.to_deltas().unwrap();
+    assert_eq!(first, 1234);
+    assert_eq!(deltas, vec![0]);
+    assert_eq!(AlignedUnthBuf::from_deltas(single.get_element_bits(), first, &deltas), single);
+    
+    let descending = AlignedUnthBuf::new_from_sized_iter(Bits::new(4).unwrap(), [3, 4, 2].into_iter());
+    assert_eq!(descending.to_deltas().unwrap_err(), UnthBufError::NotAscending { index: 2 });
+}
+
+#[test]
+fn apply_mask() {
+    let bits = Bits::new(6).unwrap();
+    let values = (0..200).map(|i| i * 13 % 64);
+    
+    let mut aligned = AlignedUnthBuf::new_from_sized_iter(bits, values.clone());
+    let mut packed = PackedUnthBuf::new_from_sized_iter(bits, values.clone());
+    aligned.apply_mask(0b101010);
+    packed.apply_mask(0b101010);
+    
+    assert!(aligned.iter().eq(values.clone().map(|v| v & 0b101010)));
+    assert!(packed.iter().eq(values.clone().map(|v| v & 0b101010)));
+    assert_eq!(aligned.validate(), Ok(()));
+    
+    // Other layouts mask element by element.
+    let mut planes = UnthBuf::<InterleavedLayout>::new_from_sized_iter(bits, values.clone());
+    let mut cells = UnthBuf::<CellPerElementLayout>::new_from_sized_iter(bits, values.clone());
+    planes.apply_mask(0b101010);
+    cells.apply_mask(0b101010);
+    assert!(planes.iter().eq(values.clone().map(|v| v & 0b101010)));
+    assert!(cells.iter().eq(values.map(|v| v & 0b101010)));
+}
+
+#[test]
+fn add_scalar() {
+    let bits = Bits::new(4).unwrap();
+    let values = [0, 1, 13, 14, 15];
+    
+    let mut saturating = PackedUnthBuf::new_from_sized_iter(bits, values.into_iter());
+    saturating.saturating_add_scalar(2);
+    assert!(saturating.iter().eq([2, 3, 15, 15, 15].into_iter()));
+    saturating.saturating_add_scalar(usize::MAX);
+    assert!(saturating.iter().all(|v| v == 15));
+    
+    let mut wrapping = AlignedUnthBuf::new_from_sized_iter(bits, values.into_iter());
+    wrapping.wrapping_add_scalar(2);
+    assert!(wrapping.iter().eq([2, 3, 15, 0, 1].into_iter()));
+}
+
+#[test]
+fn bit_access() {
+    let bits = Bits::new(3).unwrap();
+    let mut buf = PackedUnthBuf::new_from_sized_iter(bits, [0b001, 0b110, 0b000].into_iter());
+    assert_eq!(buf.bit_len(), 9);
+    
+    let stream: Vec<bool> = (0..buf.bit_len()).map(|bit| buf.get_bit(bit).unwrap()).collect();
+    assert_eq!(stream, [true, false, false, false, true, true, false, false, false]);
+    assert_eq!(buf.get_bit(buf.get_total_bit_count()), None);
+    
+    buf.set_bit(8, true);
+    buf.set_bit(4, false);
+    assert!(buf.iter().eq([0b001, 0b100, 0b100].into_iter()));
+}
+
+#[test]
+fn iter_size_hint() {
+    let buf = PackedUnthBuf::new(Bits::new(3).unwrap(), 10);
+    let mut iter = buf.iter();
+    assert_eq!(iter.size_hint(), (10, Some(10)));
+    
+    iter.next();
+    assert_eq!(iter.size_hint(), (iter.len(), Some(iter.len())));
+    assert_eq!(iter.len(), 9);
+    
+    let collected: Vec<usize> = iter.collect();
+    assert_eq!(collected.len(), 9);
+}
+
+#[test]
+fn iter_nth() {
+    let buf = AlignedUnthBuf::new_from_sized_iter(Bits::new(7).unwrap(), (0..100).map(|i| i * 3 % 128));
+    
+    let mut iter = buf.iter();
+    assert_eq!(iter.nth(10), buf.get(10));
+    assert_eq!(iter.nth(1), buf.get(12));
+    assert_eq!(iter.nth(86), buf.get(99));
+    assert_eq!(iter.nth(1), None);
+    assert_eq!(buf.iter().nth(usize::MAX), None);
+    
+    assert!(buf.iter().step_by(7).eq((0..100).step_by(7).map(|i| buf.get(i).unwrap())));
+}
+
+#[test]
+#[ignore = "benchmark"]
+pub fn bench_iter_owned() {
+    use std::time::Instant;
+    
+    let n = ITERATIONS;
+    let bitsize = BITSIZE.try_into().unwrap();
+    let buf = UnthBuf::<PackedLayout>::new_from_capacity_and_iter(bitsize, n, (0..n).map(|i| i % 32));
+    
+    println!();
+    let now = Instant::now();
+    let sum: usize = buf.iter().sum();
+    let elapsed = now.elapsed();
+    println!("Iterating {} borrowed values took {} ms / {} ns per int. ({sum})", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+    
+    let now = Instant::now();
+    let sum: usize = buf.into_iter().sum();
+    let elapsed = now.elapsed();
+    println!("Iterating {}    owned values took {} ms / {} ns per int. ({sum})", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+}
+
+#[test]
+fn bool_vec() {
+    let bools = [true, false, false, true, true, false, true];
+    let buf = PackedUnthBuf::from_bool_vec(Bits::new(1).unwrap(), &bools);
+    assert!(buf.iter().eq([1, 0, 0, 1, 1, 0, 1].into_iter()));
+    assert_eq!(buf.to_bool_vec(), bools);
+}
+
+#[test]
+fn windows() {
+    let buf = AlignedUnthBuf::new_from_sized_iter(Bits::new(4).unwrap(), [1, 2, 3, 4].into_iter());
+    let windows: Vec<Vec<usize>> = buf.windows(3).collect();
+    assert_eq!(windows, vec![vec![1, 2, 3], vec![2, 3, 4]]);
+    assert_eq!(buf.windows(4).count(), 1);
+    assert_eq!(buf.windows(5).count(), 0);
+}
+
+#[test]
+fn packed_full_width() {
+    let bits = Bits::new(usize::BITS as u8).unwrap();
+    let mut buf = PackedUnthBuf::new(bits, 9);
+    assert_eq!(buf.raw_len(), 9);
+    
+    for index in [0, 1, 4, 8] {
+        buf.set(index, usize::MAX).unwrap();
+    }
+    buf.set(5, 0x1234_5678).unwrap();
+    
+    assert!(buf.iter().eq([usize::MAX, usize::MAX, 0, 0, usize::MAX, 0x1234_5678, 0, 0, usize::MAX].into_iter()));
+}
+
+#[test]
+fn packed_large_bits_all_offsets() {
+    for bits in [33, 47, 59, 63, usize::BITS as u8] {
+        // Enough elements for every element to start at every possible offset.
+        let capacity = usize::BITS as usize + 1;
+        let mut buf = PackedUnthBuf::new(bits.try_into().unwrap(), capacity);
+        let mask = buf.get_element_mask();
+        let value = |index: usize| (usize::MAX / 3).rotate_left(index as u32) & mask;
+        
+        for index in buf.get_indices() {
+            buf.set(index, value(index)).unwrap();
+        }
+        for index in buf.get_indices() {
+            assert_eq!(buf.get(index), Some(value(index)), "{bits} bits @ {index}");
+        }
+        assert_eq!(buf.validate(), Ok(()));
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn fits() {
+    let buf = AlignedUnthBuf::new(Bits::new(3).unwrap(), 1);
+    assert!(buf.fits(7));
+    assert!(!buf.fits(8));
+    assert_eq!(buf.fits(8), buf.can_element_fit(8));
+}
+
+#[test]
+fn cell_access() {
+    let mut buf = AlignedUnthBuf::new(Bits::new(8).unwrap(), 16);
+    let location = buf.location_of(9);
+    
+    *buf.cell_mut(location.cell).unwrap() |= 0xAB << location.offset;
+    assert_eq!(buf.get(9), Some(0xAB));
+    assert_eq!(buf.cell(location.cell), Some(0xAB << location.offset));
+    assert_eq!(buf.cell(buf.raw_len()), None);
+    assert!(buf.cell_mut(buf.raw_len()).is_none());
+}
+
+#[test]
+fn decode_into() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(5).unwrap(), (0..10).map(|i| i * 3));
+    
+    let mut out = [usize::MAX; 16];
+    assert_eq!(buf.decode_into(&mut out), 10);
+    assert_eq!(out[..10], [0, 3, 6, 9, 12, 15, 18, 21, 24, 27]);
+    assert_eq!(out[10], usize::MAX);
+    
+    let mut out = [0; 4];
+    assert_eq!(buf.decode_into(&mut out), 4);
+    assert_eq!(out, [0, 3, 6, 9]);
+}
+
+#[test]
+fn encode_from_slice() {
+    let mut buf = AlignedUnthBuf::new_with_default(Bits::new(4).unwrap(), 6, 9);
+    
+    buf.encode_from_slice(&[1, 2, 3]).unwrap();
+    assert!(buf.iter().eq([1, 2, 3, 9, 9, 9].into_iter()));
+    
+    assert_eq!(buf.encode_from_slice(&[4, 5, 16, 17]), Err(UnthBufError::ValueDoesNotFit { index: 2, value: 16 }));
+    assert_eq!(buf.encode_from_slice(&[0; 7]), Err(UnthBufError::LengthMismatch { expected: 6, actual: 7 }));
+    assert!(buf.iter().eq([1, 2, 3, 9, 9, 9].into_iter()));
+}
+
+#[test]
+fn shared_buffer() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(6).unwrap(), (0..64).rev());
+    let shared = SharedUnthBuf::from(buf.clone());
+    
+    let handles: Vec<_> = (0..4).map(|_| {
+        let shared = shared.clone();
+        std::thread::spawn(move || shared.iter().sum::<usize>())
+    }).collect();
+    
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), buf.iter().sum::<usize>());
+    }
+    assert_eq!(shared.len(), 64);
+    assert_eq!(shared.get(0), Some(63));
+    assert_eq!(shared.clone().view().data.as_ptr(), shared.view().data.as_ptr());
+}
+
+#[test]
+fn atomic_buffer() {
+    let buf = AtomicUnthBuf::new(Bits::new(5).unwrap(), 100);
+    
+    std::thread::scope(|scope| {
+        for thread in 0..4 {
+            let buf = &buf;
+            scope.spawn(move || {
+                for index in (thread..100).step_by(4) {
+                    buf.set(index, index % 32).unwrap();
+                }
+            });
+        }
+    });
+    
+    assert_eq!(buf.compare_exchange(33, 1, 7), Ok(1));
+    assert_eq!(buf.compare_exchange(33, 1, 9), Err(7));
+    assert!(buf.set(100, 0).is_err());
+    
+    let buf = buf.into_inner();
+    assert!(buf.iter().eq((0..100).map(|i| if i == 33 {7} else {i % 32})));
+}
+
+#[test]
+fn send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    
+    assert_send_sync::<AlignedUnthBuf>();
+    assert_send_sync::<PackedUnthBuf>();
+    assert_send_sync::<UnthBufIter<'static, AlignedLayout>>();
+    assert_send_sync::<UnthBufIter<'static, PackedLayout>>();
+    assert_send_sync::<UnthBufRef<'static, PackedLayout>>();
+    assert_send_sync::<UnthBufMut<'static, PackedLayout>>();
+    assert_send_sync::<SharedUnthBuf<PackedLayout>>();
+    assert_send_sync::<AtomicUnthBuf>();
+}
+
+#[test]
+fn convert_layouts() {
+    let bits = Bits::new(9).unwrap();
+    let aligned = AlignedUnthBuf::new_from_sized_iter(bits, (0..77).map(|i| i * 31 % 512));
+    let packed = PackedUnthBuf::new_from_sized_iter(bits, (0..77).map(|i| i * 31 % 512));
+    
+    assert_eq!(convert_layout::<AlignedLayout, PackedLayout>(&aligned), packed);
+    assert_eq!(convert_layout::<PackedLayout, AlignedLayout>(&packed), aligned);
+    assert_eq!(convert_layout::<AlignedLayout, AlignedLayout>(&aligned), aligned);
+    assert_eq!(convert_layout::<PackedLayout, PackedLayout>(&packed), packed);
+}
+
+#[test]
+fn const_bits() {
+    let mut buf = UnthBufConst::<4, PackedLayout>::new(100);
+    for index in 0..100 {
+        buf.set(index, index % 16).unwrap();
+    }
+    assert!(buf.set(0, 16).is_err());
+    
+    let runtime: PackedUnthBuf = buf.clone().into();
+    assert!(runtime.iter().eq((0..100).map(|i| i % 16)));
+    assert_eq!(UnthBufConst::<4, PackedLayout>::try_from(runtime.clone()), Ok(buf));
+    assert!(UnthBufConst::<5, PackedLayout>::try_from(runtime).is_err());
+}
+
+#[test]
+#[ignore = "benchmark"]
+pub fn bench_io_const() {
+    use std::time::Instant;
+    
+    let n = ITERATIONS;
+    
+    println!();
+    let mut runtime = UnthBuf::<AlignedLayout>::new(4.try_into().unwrap(), n);
+    let now = Instant::now();
+    for i in 0..n {
+        runtime.set(i, i % 16).unwrap();
+    }
+    let sum: usize = (0..n).map(|i| runtime.get(i).unwrap()).sum();
+    let elapsed = now.elapsed();
+    println!("Writing+Reading {} runtime 4-bit values took {} ms / {} ns per int. ({sum})", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+    
+    let mut constant = UnthBufConst::<4, AlignedLayout>::new(n);
+    let now = Instant::now();
+    for i in 0..n {
+        constant.set(i, i % 16).unwrap();
+    }
+    let sum: usize = (0..n).map(|i| constant.get(i).unwrap()).sum();
+    let elapsed = now.elapsed();
+    println!("Writing+Reading {}   const 4-bit values took {} ms / {} ns per int. ({sum})", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+}
+
+#[test]
+#[cfg(feature = "prefetch")]
+fn iter_prefetched() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(13).unwrap(), (0..5000).map(|i| i % 8192));
+    assert!(buf.iter_prefetched().eq(buf.iter()));
+    
+    let buf = AlignedUnthBuf::new_from_sized_iter(Bits::new(13).unwrap(), (0..5000).map(|i| i % 8192));
+    assert!(buf.iter_prefetched().eq(buf.iter()));
+    
+    let buf = InterleavedUnthBuf::new_from_sized_iter(Bits::new(13).unwrap(), (0..5000).map(|i| i % 8192));
+    assert!(buf.iter_prefetched().eq(buf.iter()));
+}
+
+#[test]
+fn element_location_cells() {
+    let bits = Bits::new(13).unwrap();
+    let per_cell = (BITS_PER_CELL / 13) as usize;
+    assert_eq!(AlignedUnthBuf::new(bits, 100).location_of(per_cell).cell(), 1);
+    assert_eq!(AlignedUnthBuf::new(bits, 100).location_of(per_cell - 1).cell(), 0);
+    assert_eq!(PackedUnthBuf::new(bits, 100).location_of(per_cell).cell(), 13 * per_cell / BITS_PER_CELL as usize);
+    assert_eq!(InterleavedUnthBuf::new(bits, 100).location_of(BITS_PER_CELL as usize).cell(), 1);
+    assert_eq!(InterleavedUnthBuf::new(bits, 100).location_of(per_cell).cell(), 0);
+}
+
+#[test]
+fn rebit_in_place() {
+    let mut buf = PackedUnthBuf::new_from_sized_iter(Bits::new(5).unwrap(), (0..40).map(|i| i % 32));
+    
+    buf.rebit_in_place(Bits::new(13).unwrap()).unwrap();
+    assert_eq!(buf.get_element_bits().get(), 13);
+    assert!(buf.iter().eq((0..40).map(|i| i % 32)));
+    buf.set(0, 8191).unwrap();
+    
+    assert_eq!(buf.rebit_in_place(Bits::new(12).unwrap()), Err(UnthBufError::ValueDoesNotFit { index: 0, value: 8191 }));
+    assert_eq!(buf.get_element_bits().get(), 13);
+    
+    buf.set(0, 31).unwrap();
+    buf.rebit_in_place(Bits::new(5).unwrap()).unwrap();
+    assert_eq!(buf.get_element_bits().get(), 5);
+    assert!(buf.iter().eq((0..40).map(|i| if i == 0 {31} else {i % 32})));
+}
+
+#[test]
+fn from_byte_stream() {
+    // Three 5-bit elements, LSB-first: 0b10101, 0b00011, 0b11111
+    let bytes = [0b0111_0101, 0b0111_1100];
+    let buf = PackedUnthBuf::from_byte_stream(Bits::new(5).unwrap(), 3, &bytes).unwrap();
+    assert!(buf.iter().eq([0b10101, 0b00011, 0b11111].into_iter()));
+    
+    assert_eq!(
+        PackedUnthBuf::from_byte_stream(Bits::new(5).unwrap(), 4, &bytes),
+        Err(UnthBufError::LengthMismatch { expected: 3, actual: 2 })
+    );
+}
+
+#[test]
+fn to_byte_stream() {
+    let buf = AlignedUnthBuf::new_from_sized_iter(Bits::new(5).unwrap(), [0b10101, 0b00011, 0b11111].into_iter());
+    assert_eq!(buf.to_byte_stream(), [0b0111_0101, 0b0111_1100]);
+    
+    for bits in [1, 7, 8, 13, 33, usize::BITS as u8] {
+        let bits = Bits::new(bits).unwrap();
+        let mask = UnthBuf::<PackedLayout>::mask_from_bits(bits.get());
+        let packed = PackedUnthBuf::new_from_sized_iter(bits, (0..101).map(|i| (i * 0x9E37_79B9) & mask));
+        
+        let bytes = packed.to_byte_stream();
+        assert_eq!(bytes.len(), (101 * bits.get() as usize).div_ceil(8));
+        assert_eq!(AlignedUnthBuf::from_byte_stream(bits, 101, &bytes).unwrap().to_byte_stream(), bytes);
+        assert_eq!(PackedUnthBuf::from_byte_stream(bits, 101, &bytes).unwrap(), packed);
+    }
+}
+
+#[test]
+fn writer() {
+    let mut writer = UnthBufWriter::new();
+    for value in [3, 17, 0, 9] {
+        writer.push(value);
+    }
+    assert_eq!(writer.len(), 4);
+    
+    let buf: PackedUnthBuf = writer.finish().unwrap();
+    assert_eq!(buf.get_element_bits().get(), 5);
+    assert!(buf.iter().eq([3, 17, 0, 9].into_iter()));
+    
+    assert_eq!(UnthBufWriter::new().finish::<AlignedLayout>(), Err(UnthBufError::ZeroCapacity));
+}
+
+#[test]
+fn binary_search() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(6).unwrap(), [1, 3, 3, 3, 8, 13, 40].into_iter());
+    
+    assert_eq!(buf.binary_search(8), Ok(4));
+    assert_eq!(buf.binary_search(40), Ok(6));
+    assert!(matches!(buf.binary_search(3), Ok(1..=3)));
+    assert_eq!(buf.binary_search(0), Err(0));
+    assert_eq!(buf.binary_search(9), Err(5));
+    assert_eq!(buf.binary_search(63), Err(7));
+}
+
+#[test]
+fn is_sorted() {
+    let sorted = AlignedUnthBuf::new_from_sized_iter(Bits::new(6).unwrap(), [1, 3, 3, 8].into_iter());
+    let unsorted = AlignedUnthBuf::new_from_sized_iter(Bits::new(6).unwrap(), [1, 3, 2, 8].into_iter());
+    
+    assert!(sorted.is_sorted());
+    assert!(!unsorted.is_sorted());
+    assert!(!sorted.is_sorted_by(|a, b| a < b));
+    assert!(AlignedUnthBuf::new(Bits::new(1).unwrap(), 1).is_sorted());
+}
+
+#[test]
+fn sort() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(7).unwrap(), (0..300).map(|i| i * 37 % 128));
+    
+    let sorted = buf.sorted();
+    assert!(sorted.is_sorted());
+    assert_eq!(sorted.to_rle().len(), 128);
+    
+    let mut in_place = buf.clone();
+    in_place.sort_in_place();
+    assert_eq!(in_place, sorted);
+}
+
+#[test]
+fn sort_wide() {
+    let bits = Bits::new(COUNTING_SORT_MAX_BITS + 1).unwrap();
+    let buf = AlignedUnthBuf::new_from_sized_iter(bits, (0..300).map(|i| i * 7919 % (1 << 17)));
+    let sorted = buf.sorted();
+    assert!(sorted.is_sorted());
+    
+    let mut expected: Vec<usize> = buf.iter().collect();
+    expected.sort();
+    assert!(sorted.iter().eq(expected.into_iter()));
+}
+
+#[test]
+#[ignore = "benchmark"]
+pub fn bench_sort() {
+    use std::time::Instant;
+    use rand::prelude::*;
+    
+    let mut rng = rand::rngs::StdRng::seed_from_u64(RNG_SEED);
+    let n = 1_000_000;
+    let values: Vec<usize> = (0..n).map(|_| rng.gen_range(0..64)).collect();
+    
+    println!();
+    let mut buf = UnthBuf::<PackedLayout>::new_from_sized_iter(6.try_into().unwrap(), values.iter().copied());
+    let now = Instant::now();
+    buf.sort_in_place();
+    let elapsed = now.elapsed();
+    println!("Counting-sorting {} 6-bit values took {} ms / {} ns per int.", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+    
+    let mut vec = values.clone();
+    let now = Instant::now();
+    vec.sort();
+    let elapsed = now.elapsed();
+    println!("Vec-sorting      {} 6-bit values took {} ms / {} ns per int.", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+}
+
+#[test]
+fn dedup() {
+    let bits = Bits::new(4).unwrap();
+    
+    let runs = PackedUnthBuf::new_from_sized_iter(bits, [1, 1, 2, 2, 2, 1, 3, 3].into_iter());
+    assert!(runs.dedup().iter().eq([1, 2, 1, 3].into_iter()));
+    
+    let equal = PackedUnthBuf::new_with_default(bits, 50, 7);
+    assert!(equal.dedup().iter().eq([7].into_iter()));
+    
+    let distinct = PackedUnthBuf::new_from_sized_iter(bits, 0..16);
+    assert_eq!(distinct.dedup(), distinct);
+}
+
+#[test]
+fn required_bits() {
+    let bits = Bits::new(16).unwrap();
+    assert_eq!(AlignedUnthBuf::new(bits, 10).required_bits().get(), 1);
+    assert_eq!(AlignedUnthBuf::new_from_sized_iter(bits, [3, 1, 200].into_iter()).required_bits().get(), 8);
+    assert_eq!(AlignedUnthBuf::new_from_sized_iter(bits, [256, 1].into_iter()).required_bits().get(), 9);
+}
+
+#[test]
+fn resize() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(5).unwrap(), 0..20);
+    
+    let grown = buf.resize(30, 31).unwrap();
+    assert!(grown.iter().eq((0..20).chain([31; 10])));
+    
+    let shrunk = buf.resize(7, 0).unwrap();
+    assert!(shrunk.iter().eq(0..7));
+    
+    assert_eq!(buf.resize(0, 0), Err(UnthBufError::ZeroCapacity));
+    assert_eq!(buf.resize(30, 32), Err(UnthBufError::ValueDoesNotFit { index: 20, value: 32 }));
+    assert_eq!(buf.resize(usize::MAX, 0), Err(UnthBufError::CapacityOverflow { capacity: usize::MAX }));
+}
+
+#[test]
+fn truncate_and_grow() {
+    let buf = AlignedUnthBuf::new_from_sized_iter(Bits::new(5).unwrap(), 0..20);
+    
+    assert_eq!(buf.truncate(5), buf.resize(5, 0));
+    assert_eq!(buf.truncate(50).unwrap(), buf);
+    assert_eq!(buf.truncate(0), Err(UnthBufError::ZeroCapacity));
+    
+    assert_eq!(buf.grow(3, 9), buf.resize(23, 9));
+    assert!(buf.grow(3, 32).is_err());
+    assert_eq!(buf.grow(usize::MAX, 0), Err(UnthBufError::CapacityOverflow { capacity: usize::MAX }));
+}
+
+#[test]
+fn byte_slice() {
+    let mut buf = AlignedUnthBuf::new(Bits::new(8).unwrap(), 20);
+    assert_eq!(buf.as_byte_slice().len(), buf.raw_byte_len());
+    
+    buf.set(0, 0xAB).unwrap();
+    assert_eq!(buf.as_byte_slice()[..size_of::<usize>()], buf.raw()[0].to_ne_bytes());
+    
+    buf.as_byte_slice_mut().fill(0);
+    assert!(buf.iter().all(|v| v == 0));
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn bytemuck_cells() {
+    let mut buf = PackedUnthBuf::new(Bits::new(5).unwrap(), 40);
+    buf.fill_from((0..40).map(|i| i % 32));
+    
+    let bytes = buf.cast_cells();
+    assert_eq!(bytes, buf.as_byte_slice());
+    
+    let copy = PackedUnthBuf::from_cell_bytes(buf.get_element_bits(), 40, bytes).unwrap();
+    assert_eq!(copy, buf);
+    
+    assert_eq!(
+        PackedUnthBuf::from_cell_bytes(buf.get_element_bits(), 40, &bytes[1..]),
+        Err(UnthBufError::MisalignedBytes)
+    );
+    
+    // A single cell must not pass for a capacity whose cell count overflows.
+    let cell = &bytes[..size_of::<usize>()];
+    let bits = Bits::new(BITS_PER_CELL).unwrap();
+    let capacity = usize::MAX / BITS_PER_CELL as usize + 1;
+    assert_eq!(PackedUnthBuf::from_cell_bytes(bits, capacity, cell), Err(UnthBufError::CapacityOverflow { capacity }));
+    assert_eq!(
+        PackedUnthBuf::from_cell_bytes(Bits::new(BITS_PER_CELL + 1).unwrap(), 1, cell),
+        Err(UnthBufError::InvalidBits { bits: BITS_PER_CELL + 1 })
+    );
+}
+
+#[test]
+fn eq_slice_and_vec() {
+    let mut buf = AlignedUnthBuf::new(Bits::new(4).unwrap(), 3);
+    buf.fill_from([1, 2, 3].into_iter());
+    
+    assert_eq!(buf, vec![1, 2, 3]);
+    assert_eq!(vec![1, 2, 3], buf);
+    assert_eq!(buf, [1, 2, 3][..]);
+    assert_eq!([1, 2, 3][..], buf);
+    
+    assert_ne!(buf, vec![1, 2]);
+    assert_ne!(buf, vec![1, 2, 3, 0]);
+    assert_ne!(vec![1, 2, 4], buf);
+}
+
+#[test]
+fn collect() {
+    let buf: AlignedUnthBuf = [3, 9, 1].into_iter().collect();
+    assert_eq!(buf.get_element_bits().get(), 4);
+    assert_eq!(buf, vec![3, 9, 1]);
+    
+    let buf: PackedUnthBuf = vec![0; 10].into_iter().collect();
+    assert_eq!(buf.get_element_bits().get(), 1);
+    assert_eq!(buf.len(), 10);
+}
+
+#[test]
+#[should_panic(expected = "empty iterator")]
+fn collect_empty() {
+    let _: AlignedUnthBuf = core::iter::empty().collect();
+}
+
+#[test]
+fn growable_extend() {
+    let mut buf = GrowableUnthBuf::<PackedLayout>::new(Bits::new(3).unwrap(), 2);
+    buf.extend(0..5);
+    buf.extend((5..8).filter(|_| true));
+    buf.extend([1, 2, 3, 4, 5, 6, 7].into_iter().cycle().take(20));
+    
+    assert_eq!(buf.len(), 28);
+    assert!(buf.capacity() >= 28);
+    assert!(buf.iter().eq((0..8).chain([1, 2, 3, 4, 5, 6, 7].into_iter().cycle().take(20))));
+    assert_eq!(buf.push(8), Err(UnthBufError::ValueDoesNotFit { index: 28, value: 8 }));
+    
+    let mut buf = GrowableUnthBuf::<AlignedLayout>::new(Bits::new(1).unwrap(), 0).with_widening(true);
+    buf.extend([1, 0, 200, 3]);
+    assert_eq!(buf.get_element_bits().get(), 8);
+    assert_eq!(buf.into_unthbuf().unwrap(), vec![1, 0, 200, 3]);
+}
+
+#[test]
+fn filter_with_indices() {
+    let mut buf = PackedUnthBuf::new(Bits::new(7).unwrap(), 30);
+    buf.fill_from((0..30).map(|i| (i * 37) % 100));
+    
+    let (kept, indices) = buf.filter_with_indices(|v| v % 2 == 0).unwrap();
+    assert_eq!(kept.len(), indices.len());
+    assert_eq!(kept.get_element_bits(), buf.get_element_bits());
+    for (value, index) in kept.iter().zip(&indices) {
+        assert_eq!(buf.get(*index), Some(value));
+        assert_eq!(value % 2, 0);
+    }
+    
+    assert_eq!(buf.filter_with_indices(|v| v > 100).unwrap_err(), UnthBufError::ZeroCapacity);
+}
+
+#[test]
+fn growable_swap_remove() {
+    let mut buf = GrowableUnthBuf::<PackedLayout>::new(Bits::new(5).unwrap(), 8);
+    buf.extend([10, 11, 12, 13, 14]);
+    
+    assert_eq!(buf.swap_remove(5), None);
+    assert_eq!(buf.swap_remove(4), Some(14));
+    assert!(buf.iter().eq([10, 11, 12, 13]));
+    
+    assert_eq!(buf.swap_remove(1), Some(11));
+    assert!(buf.iter().eq([10, 13, 12]));
+    assert_eq!(buf.len(), 3);
+    assert_eq!(buf.get(3), None);
+}
+
+#[test]
+fn growable_insert_remove() {
+    let mut buf = GrowableUnthBuf::<PackedLayout>::new(Bits::new(2).unwrap(), 2).with_widening(true);
+    buf.extend([1, 2]);
+    
+    buf.insert(0, 0).unwrap();
+    buf.insert(3, 3).unwrap();
+    buf.insert(2, 100).unwrap();
+    assert!(buf.iter().eq([0, 1, 100, 2, 3]));
+    assert_eq!(buf.get_element_bits().get(), 7);
+    
+    assert_eq!(buf.remove(5), None);
+    assert_eq!(buf.remove(2), Some(100));
+    assert_eq!(buf.remove(0), Some(0));
+    assert!(buf.iter().eq([1, 2, 3]));
+}
+
+#[test]
+#[should_panic(expected = "insertion index")]
+fn growable_insert_out_of_bounds() {
+    let mut buf = GrowableUnthBuf::<AlignedLayout>::new(Bits::new(2).unwrap(), 4);
+    buf.insert(1, 0).unwrap();
+}
+
+#[test]
+fn cursor() {
+    let mut buf = PackedUnthBuf::new(Bits::new(5).unwrap(), 10);
+    buf.fill_from(10..20);
+    
+    let mut cursor = buf.cursor();
+    assert_eq!(cursor.remaining(), 10);
+    assert_eq!(cursor.read(), Some(10));
+    
+    cursor.skip(3);
+    assert_eq!(cursor.position(), 4);
+    assert_eq!(cursor.read(), Some(14));
+    
+    cursor.seek(1);
+    assert_eq!(cursor.read(), Some(11));
+    assert_eq!(cursor.remaining(), 8);
+    
+    cursor.skip(100);
+    assert_eq!(cursor.remaining(), 0);
+    assert_eq!(cursor.read(), None);
+    
+    cursor.seek(9);
+    assert_eq!(cursor.read(), Some(19));
+    assert_eq!(cursor.read(), None);
+}
+
+#[test]
+fn first_last() {
+    let mut buf = PackedUnthBuf::new(Bits::new(7).unwrap(), 13);
+    buf.fill_from(50..63);
+    assert_eq!(buf.first(), Some(50));
+    assert_eq!(buf.last(), Some(62));
+    
+    let buf = AlignedUnthBuf::new_with_default(Bits::new(3).unwrap(), 1, 5);
+    assert_eq!(buf.first(), buf.last());
+}
+
+#[test]
+fn for_each_mut() {
+    let mut buf = PackedUnthBuf::new(Bits::new(10).unwrap(), 100);
+    buf.fill_from(0..100);
+    
+    buf.for_each_mut(|index, value| *value = *value * 5 + index);
+    assert!(buf.iter().eq((0..100).map(|i| i * 6)));
+}
+
+#[test]
+#[should_panic(expected = "does not fit")]
+fn for_each_mut_overflow() {
+    let mut buf = PackedUnthBuf::new(Bits::new(3).unwrap(), 4);
+    buf.for_each_mut(|index, value| *value = index * 4);
+}
+
+#[test]
+fn fold_and_try_fold() {
+    let mut buf = PackedUnthBuf::new(Bits::new(6).unwrap(), 20);
+    buf.fill_from(1..21);
+    
+    assert_eq!(buf.fold(0, |acc, v| acc + v), 210);
+    
+    let bounded = |acc: usize, v: usize| if acc + v > 50 {core::ops::ControlFlow::Break(acc)} else {core::ops::ControlFlow::Continue(acc + v)};
+    assert_eq!(buf.try_fold(0, bounded), core::ops::ControlFlow::Break(45));
+    assert_eq!(buf.try_fold(0, |acc, v| core::ops::ControlFlow::<usize, usize>::Continue(acc.max(v))), core::ops::ControlFlow::Continue(20));
+}
+
+#[test]
+fn reduce_and_or() {
+    let mut buf = AlignedUnthBuf::new(Bits::new(4).unwrap(), 3);
+    buf.fill_from([0b0110, 0b1100, 0b0101].into_iter());
+    assert_eq!(buf.reduce_and(), 0b0100);
+    assert_eq!(buf.reduce_or(), 0b1111);
+    
+    let buf = PackedUnthBuf::new(Bits::new(7).unwrap(), 10);
+    assert_eq!(buf.reduce_and(), 0);
+    assert_eq!(buf.reduce_or(), 0);
+}
+
+#[test]
+#[cfg(feature = "index-set")]
+fn index_set() {
+    let buf = PackedUnthBuf::from_index_set(100, &[3, 64, 99, 3]).unwrap();
+    assert_eq!(buf.get_element_bits().get(), 1);
+    assert_eq!(buf.to_index_set().unwrap(), vec![3, 64, 99]);
+    assert_eq!(buf.to_bool_vec().iter().filter(|&&b| b).count(), 3);
+    
+    assert_eq!(
+        AlignedUnthBuf::from_index_set(10, &[10]).unwrap_err(),
+        UnthBufError::RangeOutOfBounds { start: 10, end: 11, capacity: 10 }
+    );
+    assert_eq!(
+        AlignedUnthBuf::from_index_set(10, &[usize::MAX]).unwrap_err(),
+        UnthBufError::RangeOutOfBounds { start: usize::MAX, end: usize::MAX, capacity: 10 }
+    );
+    assert!(AlignedUnthBuf::new(Bits::new(2).unwrap(), 4).to_index_set().is_err());
+}
+
+#[test]
+fn iter_into_inner() {
+    let mut buf = PackedUnthBuf::new(Bits::new(5).unwrap(), 10);
+    buf.fill_from(0..10);
+    let ptr = buf.raw().as_ptr();
+    
+    let mut iter = buf.into_iter();
+    assert_eq!(iter.nth(3), Some(3));
+    
+    let inner = iter.into_inner();
+    assert!(matches!(inner, std::borrow::Cow::Owned(_)));
+    let buf = inner.into_owned();
+    assert_eq!(buf.raw().as_ptr(), ptr);
+    
+    let inner = buf.iter().into_inner();
+    assert!(matches!(inner, std::borrow::Cow::Borrowed(b) if std::ptr::eq(b, &buf)));
+}
+
+#[test]
+#[allow(deprecated)]
+fn len_and_is_empty() {
+    let buf = PackedUnthBuf::new(Bits::new(3).unwrap(), 17);
+    assert_eq!(buf.len(), 17);
+    assert_eq!(buf.get_capacity(), buf.len());
+    assert!(!buf.is_empty());
+}
+
+#[test]
+fn to_gpu_bytes() {
+    let mut buf = AlignedUnthBuf::new(Bits::new(8).unwrap(), 9);
+    buf.fill_from(1..10);
+    
+    let bytes = buf.to_gpu_bytes(16);
+    assert_eq!(bytes.len() % 16, 0);
+    assert!(bytes.len() >= buf.raw_byte_len());
+    assert_eq!(bytes[..9], [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert!(bytes[buf.raw_byte_len()..].iter().all(|&b| b == 0));
+    
+    assert_eq!(buf.to_gpu_bytes(1).len(), buf.raw_byte_len());
+}
+
+#[test]
+#[should_panic(expected = "power of two")]
+fn to_gpu_bytes_bad_alignment() {
+    AlignedUnthBuf::new(Bits::new(8).unwrap(), 9).to_gpu_bytes(12);
+}
+
+#[test]
+fn iter_cells() {
+    let buf = PackedUnthBuf::new_with_default(Bits::new(5).unwrap(), 30, 31);
+    let cells: Vec<_> = buf.iter_cells().collect();
+    assert_eq!(cells.len(), buf.raw_len());
+    assert!(cells[..cells.len() - 1].iter().all(|&(_, mask)| mask == usize::MAX));
+    
+    let tail_bits = 150 % usize::BITS as usize;
+    let (last, mask) = cells[cells.len() - 1];
+    assert_eq!(mask, (1 << tail_bits) - 1);
+    assert_eq!(last, mask);
+    
+    let buf = AlignedUnthBuf::new_with_default(Bits::new(5).unwrap(), 30, 31);
+    let per_cell = usize::BITS as usize / 5;
+    let (_, mask) = buf.iter_cells().next().unwrap();
+    assert_eq!(mask, (1 << (per_cell * 5)) - 1);
+    let (_, mask) = buf.iter_cells().last().unwrap();
+    assert_eq!(mask, (1 << ((30 - per_cell * (buf.raw_len() - 1)) * 5)) - 1);
+}
+
+#[test]
+fn replace() {
+    let mut aligned = AlignedUnthBuf::new(Bits::new(6).unwrap(), 20);
+    let mut packed = PackedUnthBuf::new(Bits::new(6).unwrap(), 20);
+    aligned.fill_from(0..20);
+    packed.fill_from(0..20);
+    
+    assert_eq!(aligned.replace(10, 63), Ok(10));
+    assert_eq!(aligned.replace(10, 1), Ok(63));
+    assert_eq!(packed.replace(10, 63), Ok(10));
+    assert_eq!(packed.replace(10, 1), Ok(63));
+    assert_eq!(packed.get(10), Some(1));
+    
+    assert_eq!(packed.replace(3, 64), Err(UnthBufError::ValueDoesNotFit { index: 3, value: 64 }));
+    assert_eq!(packed.replace(20, 0), Err(UnthBufError::RangeOutOfBounds { start: 20, end: 21, capacity: 20 }));
+    assert_eq!(packed.replace(usize::MAX, 0), Err(UnthBufError::RangeOutOfBounds { start: usize::MAX, end: usize::MAX, capacity: 20 }));
+    assert_eq!(packed.get(3), Some(3));
+}
+
+#[test]
+fn compare_and_set() {
+    let mut buf = PackedUnthBuf::new(Bits::new(3).unwrap(), 50);
+    
+    assert_eq!(buf.compare_and_set(40, 1, 5), Ok(false));
+    assert_eq!(buf.get(40), Some(0));
+    assert_eq!(buf.compare_and_set(40, 0, 5), Ok(true));
+    assert_eq!(buf.get(40), Some(5));
+    
+    assert_eq!(buf.compare_and_set(40, 8, 0), Err(UnthBufError::ValueDoesNotFit { index: 40, value: 8 }));
+    assert_eq!(buf.compare_and_set(40, 5, 8), Err(UnthBufError::ValueDoesNotFit { index: 40, value: 8 }));
+    assert!(buf.compare_and_set(50, 0, 0).is_err());
+    assert_eq!(buf.get(40), Some(5));
+}
+
+#[test]
+fn u64_cells() {
+    let mut buf = AlignedUnthBuf::new(Bits::new(7).unwrap(), 41);
+    buf.fill_from((0..41).map(|i| (i * 13) % 128));
+    
+    let cells = buf.to_u64_cells();
+    assert_eq!(cells.len(), (41 * 7usize).div_ceil(64));
+    
+    // Simulate 32-bit cells: the low and high halves of each canonical cell, in order.
+    let halves: Vec<u32> = cells.iter().flat_map(|&c| [c as u32, (c >> 32) as u32]).collect();
+    for index in 0..41 {
+        let bit = index * 7;
+        let pair = halves[bit / 32] as u64 | (*halves.get(bit / 32 + 1).unwrap_or(&0) as u64) << 32;
+        assert_eq!((pair >> (bit % 32)) & 0x7F, buf.get(index).unwrap() as u64);
+    }
+    
+    let packed = PackedUnthBuf::from_u64_cells(Bits::new(7).unwrap(), 41, &cells).unwrap();
+    assert!(packed.iter().eq(buf.iter()));
+    if usize::BITS == 64 {
+        assert!(packed.raw().iter().map(|&c| c as u64).eq(cells.iter().copied()));
+    }
+    
+    assert_eq!(
+        AlignedUnthBuf::from_u64_cells(Bits::new(7).unwrap(), 41, &cells[1..]).unwrap_err(),
+        UnthBufError::LengthMismatch { expected: 5, actual: 4 }
+    );
+    assert_eq!(
+        PackedUnthBuf::from_u64_cells(Bits::new(7).unwrap(), usize::MAX, &cells).unwrap_err(),
+        UnthBufError::CapacityOverflow { capacity: usize::MAX }
+    );
+}
+
+#[test]
+fn difference_counts() {
+    let mut a = PackedUnthBuf::new(Bits::new(4).unwrap(), 4);
+    let mut b = PackedUnthBuf::new(Bits::new(4).unwrap(), 4);
+    a.fill_from([0b0000, 0b1111, 0b1010, 0b0001].into_iter());
+    b.fill_from([0b0000, 0b0111, 0b0101, 0b0001].into_iter());
+    
+    assert_eq!(a.element_difference_count(&b), Ok(2));
+    assert_eq!(a.bit_difference_count(&b), Ok(1 + 4));
+    assert_eq!(a.bit_difference_count(&a), Ok(0));
+    
+    let c = PackedUnthBuf::new(Bits::new(4).unwrap(), 5);
+    assert_eq!(a.element_difference_count(&c), Err(UnthBufError::LengthMismatch { expected: 4, actual: 5 }));
+    let d = PackedUnthBuf::new(Bits::new(5).unwrap(), 4);
+    assert!(matches!(a.bit_difference_count(&d), Err(UnthBufError::BitsMismatch { .. })));
+}
+
+#[test]
+fn dot() {
+    let mut buf = AlignedUnthBuf::new(Bits::new(3).unwrap(), 4);
+    buf.fill_from([1, 2, 3, 7].into_iter());
+    assert_eq!(buf.dot(&[4, 5, 6, 1]), Ok(4 + 10 + 18 + 7));
+    assert_eq!(buf.dot(&[1, 1, 1]), Err(UnthBufError::LengthMismatch { expected: 4, actual: 3 }));
+    
+    let buf = PackedUnthBuf::new_with_default(Bits::new(usize::BITS as u8).unwrap(), 1, usize::MAX);
+    let max = usize::MAX as u128;
+    assert_eq!(buf.dot(&[usize::MAX]), Ok(max * max));
+}
+
+#[test]
+fn format_alternate_and_separated() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(7).unwrap(), 95..106);
+    assert_eq!(format!("{buf}"), "[u7; 11; 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105]");
+    
+    let pretty = format!("{buf:#}");
+    assert!(pretty.starts_with("[u7; 11;\n     0: 95\n     1: 96\n"));
+    assert!(pretty.ends_with("    10: 105\n]"));
+    
+    assert_eq!(Separated(&buf, "|").to_string(), "95|96|97|98|99|100|101|102|103|104|105");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "does not fit")]
+fn set_unchecked_debug_assert_fit() {
+    let mut buf = PackedUnthBuf::new(Bits::new(3).unwrap(), 10);
+    unsafe {buf.set_unchecked(4, 8)};
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "out of bounds")]
+fn set_unchecked_debug_assert_index() {
+    let mut buf = AlignedUnthBuf::new(Bits::new(3).unwrap(), 10);
+    unsafe {buf.set_unchecked(10, 0)};
+}
+
+#[test]
+fn fill_from_truncated() {
+    let mut buf = PackedUnthBuf::new(Bits::new(3).unwrap(), 4);
+    buf.fill_from_truncated([9, 1, 15, 2].into_iter());
+    assert_eq!(buf, vec![1, 1, 7, 2]);
+    
+    let mut buf = AlignedUnthBuf::new(Bits::new(3).unwrap(), 4);
+    buf.view_mut().fill_from_truncated([8, 6].into_iter());
+    assert_eq!(buf, vec![0, 6, 0, 0]);
+}
+
+#[test]
+fn get_or() {
+    let buf = PackedUnthBuf::new_with_default(Bits::new(2).unwrap(), 5, 3);
+    assert_eq!(buf.get_or(4, 0), 3);
+    assert_eq!(buf.get_or(5, 0), 0);
+    assert_eq!(buf.get_or(usize::MAX, 1000), 1000);
+}
+
+#[test]
+fn rchunks() {
+    let mut buf = PackedUnthBuf::new(Bits::new(4).unwrap(), 10);
+    buf.fill_from(0..10);
+    
+    let chunks: Vec<_> = buf.rchunks(3).collect();
+    assert_eq!(chunks, vec![vec![7, 8, 9], vec![4, 5, 6], vec![1, 2, 3], vec![0]]);
+    assert!(chunks.into_iter().rev().flatten().eq(0..10));
+    
+    assert_eq!(buf.rchunks(10).count(), 1);
+    assert_eq!(buf.rchunks(100).next(), Some((0..10).collect()));
+}
+
+#[test]
+fn argmin_argmax() {
+    let mut buf = PackedUnthBuf::new(Bits::new(4).unwrap(), 8);
+    buf.fill_from([5, 2, 9, 2, 9, 7, 3, 1].into_iter());
+    assert_eq!(buf.argmin(), 7);
+    assert_eq!(buf.argmax(), 2);
+    
+    buf.set(7, 4).unwrap();
+    assert_eq!(buf.argmin(), 1);
+    
+    let buf = AlignedUnthBuf::new(Bits::new(4).unwrap(), 5);
+    assert_eq!(buf.argmin(), 0);
+    assert_eq!(buf.argmax(), 0);
+}
+
+#[test]
+fn fill_with_layout_hook() {
+    for bits in 1..=usize::BITS as u8 {
+        let bits = Bits::new(bits).unwrap();
+        let value = UnthBuf::<PackedLayout>::mask_from_bits(bits.get()) & 0x5A5A_5A5A_5A5A_5A5A_u64 as usize | 1;
+        
+        for capacity in [1, 7, 64, 65, 200] {
+            let mut packed = PackedUnthBuf::new(bits, capacity);
+            let mut aligned = AlignedUnthBuf::new(bits, capacity);
+            packed.fill_with(value);
+            aligned.fill_with(value);
+            
+            assert!(packed.iter().all(|v| v == value), "packed {bits} bits, capacity {capacity}");
+            assert!(aligned.iter().all(|v| v == value), "aligned {bits} bits, capacity {capacity}");
+            assert_eq!(packed.validate(), Ok(()));
+            assert_eq!(aligned.validate(), Ok(()));
+        }
+    }
+}
+
+#[test]
+fn decode_into_layout_hook() {
+    for bits in [1, 3, 8, 13, 32, usize::BITS as u8] {
+        let bits = Bits::new(bits).unwrap();
+        let mask = UnthBuf::<AlignedLayout>::mask_from_bits(bits.get());
+        let mut buf = AlignedUnthBuf::new(bits, 150);
+        buf.fill_from((0..150usize).map(|i| i.wrapping_mul(2654435761) & mask));
+        
+        let mut out = vec![0; 200];
+        assert_eq!(buf.decode_into(&mut out), 150);
+        assert!(out[..150].iter().copied().eq(buf.iter()));
+        
+        let mut out = vec![0; 77];
+        assert_eq!(buf.decode_into(&mut out), 77);
+        assert!(out.iter().copied().eq(buf.iter().take(77)));
+    }
+}
+
+#[test]
+#[ignore = "benchmark"]
+pub fn bench_decode_into_aligned() {
+    use std::time::Instant;
+    
+    let n = ITERATIONS;
+    let bitsize = BITSIZE.try_into().unwrap();
+    let buf = UnthBuf::<AlignedLayout>::new_from_capacity_and_iter(bitsize, n, (0..n).map(|i| i % 32));
+    let mut out = vec![0; n];
+    
+    println!();
+    let now = Instant::now();
+    for (index, slot) in out.iter_mut().enumerate() {
+        *slot = buf.get(index).unwrap();
+    }
+    let elapsed = now.elapsed();
+    println!("Decoding {} aligned values per element took {} ms / {} ns per int.", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+    
+    let now = Instant::now();
+    buf.decode_into(&mut out);
+    let elapsed = now.elapsed();
+    println!("Decoding {} aligned values cell-wise   took {} ms / {} ns per int.", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+}
+
+#[test]
+fn new_from_fn() {
+    let buf = PackedUnthBuf::new_from_fn(Bits::new(3).unwrap(), 20, |i| i % 8);
+    assert!(buf.iter().eq((0..20).map(|i| i % 8)));
+}
+
+#[test]
+#[should_panic(expected = "for index 8 does not fit")]
+fn new_from_fn_overflow() {
+    AlignedUnthBuf::new_from_fn(Bits::new(3).unwrap(), 20, |i| i);
+}
+
+#[test]
+fn from_pattern() {
+    let bits = Bits::new(4).unwrap();
+    let expected = |pattern: &[usize]| (0..50).map(|i| pattern[i % pattern.len()]).collect::<Vec<_>>();
+    
+    // Cell-tiling fast path; the pattern length divides the elements per cell.
+    let buf = AlignedUnthBuf::from_pattern(bits, 50, &[1, 2, 3, 4]).unwrap();
+    assert_eq!(buf, expected(&[1, 2, 3, 4]));
+    assert_eq!(buf.validate(), Ok(()));
+    
+    // Generic path.
+    let buf = AlignedUnthBuf::from_pattern(bits, 50, &[5, 6, 7]).unwrap();
+    assert_eq!(buf, expected(&[5, 6, 7]));
+    let buf = PackedUnthBuf::from_pattern(bits, 50, &[1, 2, 3, 4]).unwrap();
+    assert_eq!(buf, expected(&[1, 2, 3, 4]));
+    let buf = UnthBuf::<InterleavedLayout>::from_pattern(bits, 50, &[1, 2, 3, 4]).unwrap();
+    assert_eq!(buf, expected(&[1, 2, 3, 4]));
+    let buf = UnthBuf::<CellPerElementLayout>::from_pattern(bits, 50, &[1, 2, 3, 4]).unwrap();
+    assert_eq!(buf.iter().collect::<Vec<_>>(), expected(&[1, 2, 3, 4]));
+    
+    assert_eq!(PackedUnthBuf::from_pattern(bits, 50, &[]).unwrap_err(), UnthBufError::EmptyPattern);
+    assert_eq!(PackedUnthBuf::from_pattern(bits, 50, &[1, 16]).unwrap_err(), UnthBufError::ValueDoesNotFit { index: 1, value: 16 });
+    assert_eq!(PackedUnthBuf::from_pattern(bits, 0, &[1]).unwrap_err(), UnthBufError::ZeroCapacity);
+    assert_eq!(PackedUnthBuf::from_pattern(bits, usize::MAX, &[1]).unwrap_err(), UnthBufError::CapacityOverflow { capacity: usize::MAX });
+}
+
+#[test]
+fn recollect() {
+    let bits = Bits::new(6).unwrap();
+    let aligned = AlignedUnthBuf::new_from_sized_iter(bits, (0..40).map(|i| i + 20));
+    
+    let packed: PackedUnthBuf = aligned.recollect();
+    assert_eq!(packed.get_element_bits(), bits);
+    assert!(packed.iter().eq(aligned.iter()));
+    
+    let back = packed.recollect::<AlignedLayout>();
+    assert_eq!(back, aligned);
+    
+    let packed = PackedUnthBuf::new_from_capacity_and_iter(bits, 40, (20..).take(40));
+    assert_eq!(packed.recollect::<PackedLayout>(), packed);
+}
+
+#[test]
+fn bitset_intersects_and_subset() {
+    let set = |indices: &[usize]| PackedUnthBuf::from_bool_vec(Bits::MIN, &(0..100).map(|i| indices.contains(&i)).collect::<Vec<_>>());
+    let a = set(&[1, 50, 99]);
+    let b = set(&[2, 51, 98]);
+    let c = set(&[1, 2, 50, 70, 99]);
+    
+    assert_eq!(a.intersects(&b), Ok(false));
+    assert_eq!(a.intersects(&c), Ok(true));
+    assert_eq!(b.intersects(&c), Ok(true));
+    
+    assert_eq!(a.is_subset(&c), Ok(true));
+    assert_eq!(c.is_subset(&a), Ok(false));
+    assert_eq!(b.is_subset(&c), Ok(false));
+    
+    // Stale padding must not count as set.
+    let mut dirty = set(&[]);
+    let mut full = set(&[]);
+    let (_, mask) = dirty.iter_cells().last().unwrap();
+    *dirty.cell_mut(dirty.raw_len() - 1).unwrap() = !mask;
+    *full.cell_mut(full.raw_len() - 1).unwrap() = usize::MAX;
+    assert_eq!(dirty.intersects(&full), Ok(false));
+    assert_eq!(dirty.is_subset(&a), Ok(true));
+    
+    assert!(a.intersects(&set(&[]).resize(99, 0).unwrap()).is_err());
+    assert!(a.is_subset(&PackedUnthBuf::new(Bits::new(2).unwrap(), 100)).is_err());
+}
+
+#[test]
+fn toggle() {
+    let mut buf = AlignedUnthBuf::new(Bits::MIN, 70);
+    assert_eq!(buf.toggle(65), Ok(true));
+    assert_eq!(buf.get(65), Some(1));
+    assert_eq!(buf.toggle(65), Ok(false));
+    assert_eq!(buf.get(65), Some(0));
+    
+    assert!(matches!(buf.toggle(70), Err(UnthBufError::RangeOutOfBounds { .. })));
+    assert!(matches!(buf.toggle(usize::MAX), Err(UnthBufError::RangeOutOfBounds { .. })));
+    assert!(matches!(AlignedUnthBuf::new(Bits::new(2).unwrap(), 4).toggle(0), Err(UnthBufError::BitsMismatch { .. })));
+}
+
+#[test]
+fn fill_with_max() {
+    for bits in [1, 5, 16, 31, usize::BITS as u8] {
+        let bits = Bits::new(bits).unwrap();
+        let mut packed = PackedUnthBuf::new(bits, 99);
+        let mut aligned = AlignedUnthBuf::new(bits, 99);
+        packed.fill_with_max();
+        aligned.fill_with_max();
+        
+        assert!(packed.iter().all(|v| v == packed.get_element_mask()));
+        assert!(aligned.iter().all(|v| v == aligned.get_element_mask()));
+        assert_eq!(packed.validate(), Ok(()));
+        assert_eq!(aligned.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn be_bytes() {
+    let mut buf = PackedUnthBuf::new(Bits::new(13).unwrap(), 20);
+    buf.fill_from((0..20).map(|i| i * 400));
+    
+    let bytes = buf.to_be_bytes();
+    let cell_size = size_of::<usize>();
+    assert_eq!(bytes[..10], [13, cell_size as u8, 0, 0, 0, 0, 0, 0, 0, 20]);
+    
+    // Independent of the host: the most significant byte of every cell comes first.
+    for (cell, chunk) in buf.raw().iter().zip(bytes[10..].chunks(cell_size)) {
+        let decoded = chunk.iter().fold(0usize, |acc, &byte| acc << 8 | byte as usize);
+        assert_eq!(decoded, *cell);
+        assert_eq!(chunk, cell.swap_bytes().to_le_bytes());
+    }
+    
+    assert_eq!(PackedUnthBuf::from_be_bytes(&bytes).unwrap(), buf);
+    
+    assert!(matches!(PackedUnthBuf::from_be_bytes(&bytes[..9]), Err(UnthBufError::LengthMismatch { .. })));
+    assert_eq!(PackedUnthBuf::from_be_bytes(&bytes[..bytes.len() - 1]).unwrap_err(), UnthBufError::MisalignedBytes);
+    
+    let mut extra = bytes.clone();
+    extra.extend([0; 2 * size_of::<usize>()]);
+    assert!(matches!(PackedUnthBuf::from_be_bytes(&extra), Err(UnthBufError::CellCountMismatch { .. })));
+    
+    let mut bad = bytes.clone();
+    bad[0] = usize::BITS as u8 + 1;
+    assert_eq!(PackedUnthBuf::from_be_bytes(&bad).unwrap_err(), UnthBufError::InvalidBits { bits: usize::BITS as u8 + 1 });
+    
+    let mut bad = bytes.clone();
+    bad[2..10].copy_from_slice(&u64::MAX.to_be_bytes());
+    assert!(matches!(PackedUnthBuf::from_be_bytes(&bad), Err(UnthBufError::LengthMismatch { .. })));
+}
+
+#[test]
+fn try_as_native_slice() {
+    let buf = AlignedUnthBuf::new_from_fn(Bits::new(16).unwrap(), 9, |i| i * 1000);
+    if cfg!(target_endian = "little") {
+        let slice = buf.try_as_native_slice::<u16>().unwrap();
+        assert_eq!(slice.len(), 9);
+        assert!(slice.iter().map(|&v| v as usize).eq(buf.iter()));
+    }
+    
+    assert!(buf.try_as_native_slice::<u8>().is_none());
+    assert!(buf.try_as_native_slice::<u32>().is_none());
+    
+    let bytes = AlignedUnthBuf::new_from_fn(Bits::new(8).unwrap(), 17, |i| i);
+    assert!(cfg!(target_endian = "big") || bytes.try_as_native_slice::<u8>() == Some(&(0..17).collect::<Vec<u8>>()[..]));
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn prop_sized_iter_roundtrip(bits in 1..=usize::BITS as u8, values in proptest::collection::vec(proptest::num::usize::ANY, 1..300)) {
+        let bits = Bits::new(bits).unwrap();
+        let mask = UnthBuf::<PackedLayout>::mask_from_bits(bits.get());
+        let clamped: Vec<usize> = values.iter().map(|&v| v & mask).collect();
+        
+        let packed = PackedUnthBuf::new_from_sized_iter(bits, clamped.iter().copied());
+        let aligned = AlignedUnthBuf::new_from_sized_iter(bits, clamped.iter().copied());
+        proptest::prop_assert_eq!(packed.iter().collect::<Vec<_>>(), clamped.clone());
+        proptest::prop_assert_eq!(aligned.iter().collect::<Vec<_>>(), clamped);
+        proptest::prop_assert_eq!(packed.validate(), Ok(()));
+        proptest::prop_assert_eq!(aligned.validate(), Ok(()));
+    }
+    
+    #[test]
+    fn prop_set_get_roundtrip(bits in 1..=usize::BITS as u8, capacity in 1..300usize, writes in proptest::collection::vec((proptest::num::usize::ANY, proptest::num::usize::ANY), 0..100)) {
+        let bits = Bits::new(bits).unwrap();
+        let mut packed = PackedUnthBuf::new(bits, capacity);
+        let mut aligned = AlignedUnthBuf::new(bits, capacity);
+        let mut expected = vec![0; capacity];
+        
+        for (index, value) in writes {
+            let (index, value) = (index % capacity, value & packed.get_element_mask());
+            packed.set(index, value).unwrap();
+            aligned.set(index, value).unwrap();
+            expected[index] = value;
+        }
+        
+        proptest::prop_assert_eq!(&packed, &expected);
+        proptest::prop_assert_eq!(&aligned, &expected);
+    }
+}
+
+#[test]
+fn byte_planning() {
+    let bits = |b| Bits::new(b).unwrap();
+    assert_eq!(elements_in_bytes(bits(1), 8), 64);
+    assert_eq!(elements_in_bytes(bits(3), 8), 21);
+    assert_eq!(elements_in_bytes(bits(64), 7), 0);
+    assert_eq!(elements_in_bytes(bits(64), 8), 1);
+    assert_eq!(elements_in_bytes(bits(7), 0), 0);
+    
+    assert_eq!(bytes_for_elements(bits(3), 21), Some(8));
+    assert_eq!(bytes_for_elements(bits(3), 22), Some(9));
+    assert_eq!(bytes_for_elements(bits(64), 1), Some(8));
+    assert_eq!(bytes_for_elements(bits(5), 0), Some(0));
+    assert_eq!(bytes_for_elements(bits(2), usize::MAX), None);
+    
+    assert_eq!(
+        PackedUnthBuf::from_byte_stream(bits(2), usize::MAX, &[0; 8]).unwrap_err(),
+        UnthBufError::CapacityOverflow { capacity: usize::MAX }
+    );
+    
+    for b in 1..=64 {
+        let buf = PackedUnthBuf::new(bits(b), 100);
+        assert_eq!(Some(buf.to_byte_stream().len()), bytes_for_elements(bits(b), 100));
+        assert!(elements_in_bytes(bits(b), bytes_for_elements(bits(b), 100).unwrap()) >= 100);
+    }
+}
+
+#[test]
+fn debug_element_sample() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(7).unwrap(), 0..5usize);
+    assert!(format!("{buf:?}").ends_with("elements: [0, 1, 2, 3, 4] }"));
+    
+    let buf = AlignedUnthBuf::new_from_sized_iter(Bits::new(10).unwrap(), 0..1000usize);
+    let debug = format!("{buf:?}");
+    assert!(debug.ends_with("elements: [0, 1, 2, 3, 4, 5, 6, 7, ..., 992, 993, 994, 995, 996, 997, 998, 999] }"), "{debug}");
+}
+
+#[test]
+fn builtin_layout_conformance() {
+    for bits in 1..=BITS_PER_CELL {
+        let bits = Bits::new(bits).unwrap();
+        for capacity in [1, 2, 63, 64, 65, 200] {
+            check_cell_layout_conformance::<AlignedLayout>(bits, capacity);
+            check_cell_layout_conformance::<PackedLayout>(bits, capacity);
+        }
+    }
+}
+
+/// A layout that stores one element per cell, implemented via the public surface only.
+#[cfg(test)]
+#[derive(Clone, Copy)]
+struct CellPerElementLayout;
+
+/// The location of an element of the [`CellPerElementLayout`]; just its cell.
+#[cfg(test)]
+struct CellIndex(usize);
+
+#[cfg(test)]
+impl ElementLocation for CellIndex {
+    fn cell(&self) -> usize {
+        self.0
+    }
+}
+
+#[cfg(test)]
+impl CellLayout for CellPerElementLayout {
+    type Location = CellIndex;
+    
+    const ALIGNED: bool = false;
+    
+    fn get_cell_count(capacity: usize, _bits: Bits) -> usize {
+        capacity
+    }
+    
+    fn get_exact_bit_count(buf: &UnthBuf<Self>) -> usize {
+        buf.len() * buf.get_element_bits().get() as usize
+    }
+    
+    fn get_cell_mask(buf: &UnthBufRef<'_, Self>, cell: usize) -> usize {
+        if cell < buf.len() {buf.mask()} else {0}
+    }
+    
+    fn location_of(_buf: &UnthBufRef<'_, Self>, index: usize) -> Self::Location {
+        CellIndex(index)
+    }
+    
+    unsafe fn set_unchecked(buf: &mut UnthBufMut<'_, Self>, index: usize, value: usize) {
+        *buf.data_mut().get_unchecked_mut(index) = value;
+    }
+    
+    unsafe fn get_unchecked(buf: &UnthBufRef<'_, Self>, index: usize) -> usize {
+        *buf.data().get_unchecked(index)
+    }
+}
+
+#[test]
+fn custom_layout_via_public_accessors() {
+    for bits in [1, 7, 32, BITS_PER_CELL] {
+        check_cell_layout_conformance::<CellPerElementLayout>(Bits::new(bits).unwrap(), 10);
+    }
+    
+    let buf = UnthBuf::<CellPerElementLayout>::new_from_sized_iter(Bits::new(5).unwrap(), 0..20usize);
+    let view = buf.view();
+    assert_eq!(view.data().len(), 20);
+    assert_eq!(view.mask(), 0b11111);
+    assert_eq!(view.elpc(), BITS_PER_CELL / 5);
+    assert_eq!(convert_layout::<_, PackedLayout>(&buf), (0..20).collect::<Vec<usize>>());
+}
+
+#[test]
+fn interleaved_round_trip() {
+    for bits in [1, 3, 8, 13, 33, BITS_PER_CELL] {
+        let bits = Bits::new(bits).unwrap();
+        check_cell_layout_conformance::<InterleavedLayout>(bits, 1);
+        check_cell_layout_conformance::<InterleavedLayout>(bits, 130);
+        
+        let mask = AlignedUnthBuf::mask_from_bits(bits.get());
+        let aligned = AlignedUnthBuf::new_from_fn(bits, 200, |index| index.wrapping_mul(PRIMES[20]) & mask);
+        let interleaved = InterleavedUnthBuf::from_aligned(&aligned);
+        assert_eq!(interleaved, aligned.iter().collect::<Vec<_>>());
+        assert_eq!(interleaved.to_aligned(), aligned);
+    }
+    
+    let buf = InterleavedUnthBuf::new_from_sized_iter(Bits::new(2).unwrap(), [0b01, 0b10, 0b11, 0b00].into_iter());
+    assert_eq!(buf.raw(), &[0b0101, 0b0110]);
+    assert_eq!(buf.plane(0), Some(&[0b0101][..]));
+    assert_eq!(buf.plane(1), Some(&[0b0110][..]));
+    assert_eq!(buf.plane(2), None);
+}
+
+#[test]
+fn packed_msb_round_trip() {
+    for bits in [1, 3, 7, 13, 31, 33, 63, BITS_PER_CELL] {
+        let bits = Bits::new(bits).unwrap();
+        check_cell_layout_conformance::<PackedLayoutMsb>(bits, 1);
+        check_cell_layout_conformance::<PackedLayoutMsb>(bits, 130);
+        
+        let mask = PackedUnthBuf::mask_from_bits(bits.get());
+        let lsb = PackedUnthBuf::new_from_fn(bits, 200, |index| index.wrapping_mul(PRIMES[40]) & mask);
+        let msb = PackedMsbUnthBuf::from_lsb(&lsb);
+        assert_eq!(msb, lsb.iter().collect::<Vec<_>>());
+        assert_eq!(msb.to_lsb(), lsb);
+    }
+    
+    let buf = PackedMsbUnthBuf::new_from_sized_iter(Bits::new(4).unwrap(), [0x1, 0x2, 0xF].into_iter());
+    assert_eq!(buf.raw(), &[0x12F << (BITS_PER_CELL - 12)]);
+    
+    // An element straddling two cells keeps its high bits in the first cell.
+    let mut buf = PackedMsbUnthBuf::new(Bits::new(24).unwrap(), 3);
+    buf.set(2, 0xABCDEF).unwrap();
+    assert_eq!(buf.raw()[0] & 0xFFFF, 0xABCD);
+    assert_eq!(buf.raw()[1] >> (BITS_PER_CELL - 8), 0xEF);
+}
+
+#[test]
+fn gather() {
+    let buf = PackedUnthBuf::new_from_sized_iter(Bits::new(13).unwrap(), (0..500usize).map(|i| i * 7));
+    let indices = [499, 0, 250, 250, 13];
+    let mut out = [usize::MAX; 6];
+    buf.gather(&indices, &mut out).unwrap();
+    assert_eq!(out, [499 * 7, 0, 250 * 7, 250 * 7, 13 * 7, usize::MAX]);
+    
+    // The prefetched cells follow the layout, whose elements need not lie in packed order.
+    let planes = UnthBuf::<InterleavedLayout>::new_from_sized_iter(Bits::new(13).unwrap(), (0..500usize).map(|i| i * 7));
+    planes.gather(&indices, &mut out).unwrap();
+    assert_eq!(out[..5], [499 * 7, 0, 250 * 7, 250 * 7, 13 * 7]);
+    
+    assert_eq!(buf.gather(&indices, &mut [0; 4]), Err(UnthBufError::LengthMismatch { expected: 5, actual: 4 }));
+    assert_eq!(buf.gather(&[3, 500, 501], &mut out), Err(UnthBufError::RangeOutOfBounds { start: 500, end: 501, capacity: 500 }));
+    assert_eq!(buf.gather(&[usize::MAX], &mut out), Err(UnthBufError::RangeOutOfBounds { start: usize::MAX, end: usize::MAX, capacity: 500 }));
+    assert_eq!(buf.gather(&[], &mut []), Ok(()));
+}
+
+#[test]
+#[ignore = "benchmark"]
+pub fn bench_gather() {
+    use std::time::Instant;
+    use rand::prelude::*;
+    
+    let n = ITERATIONS;
+    let bitsize = BITSIZE.try_into().unwrap();
+    let buf = UnthBuf::<PackedLayout>::new_from_capacity_and_iter(bitsize, n, (0..n).map(|i| i % 32));
+    let mut rng = rand::thread_rng();
+    let indices: Vec<usize> = (0..n).map(|_| rng.gen_range(0..n)).collect();
+    let mut out = vec![0; n];
+    
+    println!();
+    let now = Instant::now();
+    for (slot, &index) in out.iter_mut().zip(&indices) {
+        *slot = buf.get(index).unwrap();
+    }
+    std::hint::black_box(&out);
+    let elapsed = now.elapsed();
+    println!("Reading {} random values via get    took {} ms / {} ns per int.", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+    
+    let now = Instant::now();
+    buf.gather(&indices, &mut out).unwrap();
+    std::hint::black_box(&out);
+    let elapsed = now.elapsed();
+    println!("Reading {} random values via gather took {} ms / {} ns per int.", n, elapsed.as_millis(), elapsed.div_f64(n as f64).as_nanos());
+}
+
+#[test]
+fn scatter() {
+    // 13-bit elements straddle cells, so neighbouring writes share cells.
+    let mut buf = PackedUnthBuf::new(Bits::new(13).unwrap(), 100);
+    let indices: Vec<usize> = (0..100).rev().collect();
+    let values: Vec<usize> = indices.iter().map(|&i| 0x1FFF - i).collect();
+    buf.scatter(&indices, &values).unwrap();
+    assert!(buf.iter().enumerate().all(|(i, element)| element == 0x1FFF - i));
+    buf.validate().unwrap();
+    
+    // Overlapping cells: elements 4 and 5 straddle the boundary between cells 0 and 1.
+    let mut buf = PackedUnthBuf::new(Bits::new(13).unwrap(), 10);
+    buf.scatter(&[5, 4, 6, 3], &[0x1AAA, 0x1555, 0x1FFF, 0x0001]).unwrap();
+    assert_eq!(buf, [0, 0, 0, 0x0001, 0x1555, 0x1AAA, 0x1FFF, 0, 0, 0][..]);
+    
+    // Repeated indices; the later value wins.
+    buf.scatter(&[2, 2], &[7, 9]).unwrap();
+    assert_eq!(buf.get(2), Some(9));
+    
+    // All-or-nothing; nothing is written if anything is invalid.
+    let before = buf.clone();
+    assert_eq!(buf.scatter(&[0, 1], &[1]), Err(UnthBufError::LengthMismatch { expected: 2, actual: 1 }));
+    assert_eq!(buf.scatter(&[0, 10], &[1, 1]), Err(UnthBufError::RangeOutOfBounds { start: 10, end: 11, capacity: 10 }));
+    assert_eq!(buf.scatter(&[0, usize::MAX], &[1, 1]), Err(UnthBufError::RangeOutOfBounds { start: usize::MAX, end: usize::MAX, capacity: 10 }));
+    assert_eq!(buf.scatter(&[0, 1, 7], &[1, 1, 0x2000]), Err(UnthBufError::ValueDoesNotFit { index: 7, value: 0x2000 }));
+    assert_eq!(buf, before);
+}
+
+#[test]
+fn significant_bits_histogram() {
+    let buf = AlignedUnthBuf::new_from_sized_iter(Bits::new(10).unwrap(), [0, 1, 2, 3, 4, 1023, 512, 0].into_iter());
+    let histogram = buf.significant_bits_histogram();
+    assert_eq!(histogram[..12], [2, 1, 2, 1, 0, 0, 0, 0, 0, 0, 2, 0]);
+    assert_eq!(histogram.iter().sum::<usize>(), buf.len());
+    
+    let buf = PackedUnthBuf::new_with_default(Bits::new(BITS_PER_CELL).unwrap(), 3, usize::MAX);
+    assert_eq!(buf.significant_bits_histogram()[BITS_PER_CELL as usize], 3);
+}
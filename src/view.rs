@@ -0,0 +1,362 @@
+//! Borrowed views over cells laid out like an [`UnthBuf`].
+use crate::{UnthBuf, UnthBufError, CellLayout, Bits, BITS_PER_CELL};
+
+/// A read-only view over borrowed cells, laid out according to a [`CellLayout`].
+/// 
+/// This allows reading packed data that already lives somewhere (e.g. a memory-mapped file) without allocating.
+#[derive(Clone, Copy)]
+pub struct UnthBufRef<'a, CL: CellLayout> {
+    /// Capacity of the view.
+    pub(crate) capacity: usize,
+    
+    /// Borrowed cells, containing [`Self::bits`]-sized unsigned integer elements.
+    pub(crate) data: &'a [usize],
+    
+    /// Bit-size of an individual element in [`Self::data`].
+    pub(crate) bits: Bits,
+    
+    /// Mask of bits covering a single element.
+    pub(crate) mask: usize,
+    
+    /// Elements per cell; see [`UnthBuf`].
+    pub(crate) elpc: u8,
+    
+    /// Marker for cell layout.
+    pub(crate) cell_layout: core::marker::PhantomData<CL>
+}
+
+impl<'a, CL: CellLayout> UnthBufRef<'a, CL> {
+    /// Creates a new [`UnthBufRef`] with the given `capacity` and `bits`-size, over the given cells.
+    /// 
+    /// # Errors
+    /// - If the given `capacity` is `0`.
+    /// - If the `bits`-size is larger than a cell.
+    /// - If the total amount of bits, `capacity * bits`, overflows an [`usize`].
+    /// - If the amount of cells does not match [`CellLayout::get_cell_count`].
+    pub fn new(bits: Bits, capacity: usize, cells: &'a [usize]) -> Result<Self, UnthBufError> {
+        let expected = UnthBuf::<CL>::checked_cell_count(bits, capacity)?;
+        if cells.len() != expected {
+            return Err(UnthBufError::CellCountMismatch { expected, actual: cells.len() })
+        }
+        
+        Ok(Self::from_parts(bits, capacity, cells))
+    }
+    
+    /// Assembles a [`UnthBufRef`] from its parts, deriving the mask and elements-per-cell.
+    pub(crate) fn from_parts(bits: Bits, capacity: usize, data: &'a [usize]) -> Self {
+        Self {
+            capacity,
+            data,
+            bits,
+            mask: UnthBuf::<CL>::mask_from_bits(bits.get()),
+            elpc: BITS_PER_CELL.checked_div(bits.get()).unwrap_or(0),
+            cell_layout: core::marker::PhantomData,
+        }
+    }
+    
+    /// Gets how many elements are viewed.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.capacity
+    }
+    
+    /// Always `false`, as a view cannot be of 0 capacity.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    
+    /// Returns the bit-size of the individual elements in this view.
+    #[inline(always)]
+    pub fn get_element_bits(&self) -> Bits {
+        self.bits
+    }
+    
+    /// Returns the mask of bits covering a single element, i.e. the largest value that fits.
+    #[inline(always)]
+    pub fn mask(&self) -> usize {
+        self.mask
+    }
+    
+    /// Returns how many whole elements fit into a single cell, i.e. `BITS_PER_CELL / bits`.
+    /// 
+    /// This is exact for aligned layouts; unaligned layouts may store parts of further elements per cell.
+    #[inline(always)]
+    pub fn elpc(&self) -> u8 {
+        self.elpc
+    }
+    
+    /// Returns the viewed cells.
+    #[inline(always)]
+    pub fn data(&self) -> &'a [usize] {
+        self.data
+    }
+    
+    /// Is the given index (`0..self.len()`) valid for this view?
+    #[inline(always)]
+    pub fn is_index(&self, index: usize) -> bool {
+        index < self.capacity
+    }
+    
+    /// Returns the location of the element at the given `index`.
+    pub fn location_of(&self, index: usize) -> CL::Location {
+        CL::location_of(self, index)
+    }
+    
+    /// Returns the element at the given `index`.
+    /// 
+    /// Out-of-bounds access will return [`Option::None`].
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<usize> {
+        if !self.is_index(index) {return None}
+        Some(unsafe {self.get_unchecked(index)})
+    }
+    
+    /// Returns the element at the given `index`, *without* checking bounds.
+    /// 
+    /// # Safety
+    /// If the index is not within `0..self.len()`, this function will cause undefined behaviour.
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, index: usize) -> usize {
+        CL::get_unchecked(self, index)
+    }
+    
+    /// Returns an iterator that yields all viewed elements.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = usize> + 'a
+        where CL: 'a
+    {
+        let view = *self;
+        // This is safe, as the range is exactly the valid indices.
+        (0..self.capacity).map(move |index| unsafe {view.get_unchecked(index)})
+    }
+}
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns a read-only [`UnthBufRef`] view of this buffer.
+    #[inline(always)]
+    pub fn view(&self) -> UnthBufRef<'_, CL> {
+        UnthBufRef {
+            capacity: self.capacity,
+            data: &self.data,
+            bits: self.bits,
+            mask: self.mask,
+            elpc: self.elpc,
+            cell_layout: core::marker::PhantomData,
+        }
+    }
+}
+
+/// A mutable view over borrowed cells, laid out according to a [`CellLayout`].
+/// 
+/// This allows editing packed data embedded within a larger buffer without copying it out;
+/// the view cannot grow, as it does not own its cells.
+pub struct UnthBufMut<'a, CL: CellLayout> {
+    /// Capacity of the view.
+    pub(crate) capacity: usize,
+    
+    /// Mutably borrowed cells, containing [`Self::bits`]-sized unsigned integer elements.
+    pub(crate) data: &'a mut [usize],
+    
+    /// Bit-size of an individual element in [`Self::data`].
+    pub(crate) bits: Bits,
+    
+    /// Mask of bits covering a single element.
+    pub(crate) mask: usize,
+    
+    /// Elements per cell; see [`UnthBuf`].
+    pub(crate) elpc: u8,
+    
+    /// Marker for cell layout.
+    pub(crate) cell_layout: core::marker::PhantomData<CL>
+}
+
+impl<'a, CL: CellLayout> UnthBufMut<'a, CL> {
+    /// Creates a new [`UnthBufMut`] with the given `capacity` and `bits`-size, over the given cells.
+    /// 
+    /// # Errors
+    /// - If the given `capacity` is `0`.
+    /// - If the `bits`-size is larger than a cell.
+    /// - If the total amount of bits, `capacity * bits`, overflows an [`usize`].
+    /// - If the amount of cells does not match [`CellLayout::get_cell_count`].
+    pub fn new(bits: Bits, capacity: usize, cells: &'a mut [usize]) -> Result<Self, UnthBufError> {
+        let expected = UnthBuf::<CL>::checked_cell_count(bits, capacity)?;
+        if cells.len() != expected {
+            return Err(UnthBufError::CellCountMismatch { expected, actual: cells.len() })
+        }
+        
+        Ok(Self {
+            capacity,
+            data: cells,
+            bits,
+            mask: UnthBuf::<CL>::mask_from_bits(bits.get()),
+            elpc: BITS_PER_CELL.checked_div(bits.get()).unwrap_or(0),
+            cell_layout: core::marker::PhantomData,
+        })
+    }
+    
+    /// Returns a read-only [`UnthBufRef`] view, reborrowing the cells of this view.
+    #[inline(always)]
+    pub fn view(&self) -> UnthBufRef<'_, CL> {
+        UnthBufRef {
+            capacity: self.capacity,
+            data: self.data,
+            bits: self.bits,
+            mask: self.mask,
+            elpc: self.elpc,
+            cell_layout: core::marker::PhantomData,
+        }
+    }
+    
+    /// Gets how many elements are viewed.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.capacity
+    }
+    
+    /// Always `false`, as a view cannot be of 0 capacity.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    
+    /// Checks if the given value can be stored in this view.
+    #[inline(always)]
+    pub fn can_element_fit(&self, value: usize) -> bool {
+        (value & self.mask) == value
+    }
+    
+    /// Returns the bit-size of the individual elements in this view.
+    #[inline(always)]
+    pub fn get_element_bits(&self) -> Bits {
+        self.bits
+    }
+    
+    /// Returns the mask of bits covering a single element, i.e. the largest value that fits.
+    #[inline(always)]
+    pub fn mask(&self) -> usize {
+        self.mask
+    }
+    
+    /// Returns how many whole elements fit into a single cell; see [`UnthBufRef::elpc`].
+    #[inline(always)]
+    pub fn elpc(&self) -> u8 {
+        self.elpc
+    }
+    
+    /// Returns the viewed cells.
+    #[inline(always)]
+    pub fn data(&self) -> &[usize] {
+        self.data
+    }
+    
+    /// Returns the viewed cells, mutably.
+    /// 
+    /// Writes must keep padding bits cleared; see [`CellLayout::get_cell_mask`].
+    #[inline(always)]
+    pub fn data_mut(&mut self) -> &mut [usize] {
+        self.data
+    }
+    
+    /// Is the given index (`0..self.len()`) valid for this view?
+    #[inline(always)]
+    pub fn is_index(&self, index: usize) -> bool {
+        index < self.capacity
+    }
+    
+    /// Returns the element at the given `index`.
+    /// 
+    /// Out-of-bounds access will return [`Option::None`].
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<usize> {
+        self.view().get(index)
+    }
+    
+    /// Returns the element at the given `index`, *without* checking bounds.
+    /// 
+    /// # Safety
+    /// If the index is not within `0..self.len()`, this function will cause undefined behaviour.
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, index: usize) -> usize {
+        CL::get_unchecked(&self.view(), index)
+    }
+    
+    /// Tries to set the element at the given `index` to the provided `value`.
+    /// 
+    /// # Errors
+    /// - If the value does not fit; check with [`Self::can_element_fit`].
+    /// - If the index is out of bounds; check with [`Self::is_index`].
+    #[inline]
+    pub fn set(&mut self, index: usize, value: usize) -> Result<(),&'static str> {
+        if !self.can_element_fit(value) {return Err("value does not fit")}
+        if !self.is_index(index) {return Err("index is out-of-bounds")}
+        unsafe {self.set_unchecked(index, value);}
+        Ok(())
+    }
+    
+    /// Set the element at the given `index` to the provided `value`, *without* checking bounds.
+    /// 
+    /// Debug builds assert that the index is in bounds and the value fits.
+    /// 
+    /// # Safety
+    /// If the index is not within `0..self.len()`, this function will cause undefined behaviour.
+    #[inline(always)]
+    pub unsafe fn set_unchecked(&mut self, index: usize, value: usize) {
+        debug_assert!(self.is_index(index), "index {index} is out of bounds for capacity {}", self.capacity);
+        debug_assert!(self.can_element_fit(value), "value 0x{value:X} does not fit into {} bits", self.bits);
+        
+        CL::set_unchecked(self, index, value);
+    }
+    
+    /// Fills the view with the given value.
+    /// 
+    /// Filling with `0` is a memset, which is *very* fast.
+    pub fn fill_with(&mut self, value: usize) {
+        assert!(self.can_element_fit(value), "given value does not fit");
+        
+        if value == 0 {
+            return self.fill_with_default()
+        }
+        
+        CL::fill(self, value);
+    }
+    
+    /// Fills the view with `0`, clearing everything.
+    /// 
+    /// Filling with `0` is a memset, which is *very* fast.
+    pub fn fill_with_default(&mut self) {
+        self.data.fill(0);
+    }
+    
+    /// Fills the view with as many values from the given iterator as possible.
+    /// 
+    /// The values are expected to fit into the bit-size, but are not checked; values that do not fit corrupt neighbouring elements.
+    /// Use [`Self::fill_from_truncated`] to cut them down instead.
+    pub fn fill_from(&mut self, iter: impl Iterator<Item = usize>) {
+        for (index, value) in (0..self.capacity).zip(iter).fuse() {
+            // This is safe, as the index comes from the range of valid indices.
+            unsafe {CL::set_unchecked(self, index, value)};
+        }
+    }
+    
+    /// Fills the view with as many values from the given iterator as possible,
+    /// truncating every value to the bit-size instead of spilling into neighbouring elements.
+    pub fn fill_from_truncated(&mut self, iter: impl Iterator<Item = usize>) {
+        let mask = self.mask;
+        self.fill_from(iter.map(|value| value & mask));
+    }
+}
+
+impl<CL: CellLayout> UnthBuf<CL> {
+    /// Returns a mutable [`UnthBufMut`] view of this buffer.
+    #[inline(always)]
+    pub fn view_mut(&mut self) -> UnthBufMut<'_, CL> {
+        UnthBufMut {
+            capacity: self.capacity,
+            data: &mut self.data,
+            bits: self.bits,
+            mask: self.mask,
+            elpc: self.elpc,
+            cell_layout: core::marker::PhantomData,
+        }
+    }
+}
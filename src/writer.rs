@@ -0,0 +1,61 @@
+//! Incremental construction of [`UnthBuf`]s.
+use crate::{UnthBuf, UnthBufError, CellLayout};
+
+/// Collects elements one by one, without knowing their bit-size up front,
+/// then packs them into a minimally-sized [`UnthBuf`] via [`Self::finish`].
+#[derive(Debug, Clone, Default)]
+pub struct UnthBufWriter {
+    /// The elements pushed so far.
+    pub(crate) values: Vec<usize>,
+    
+    /// The largest element pushed so far.
+    pub(crate) max: usize,
+}
+
+impl UnthBufWriter {
+    /// Creates a new, empty [`UnthBufWriter`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Appends the given value.
+    pub fn push(&mut self, value: usize) {
+        self.max = self.max.max(value);
+        self.values.push(value);
+    }
+    
+    /// Gets how many values were pushed so far.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    
+    /// Returns `true` if no values were pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    
+    /// Packs all pushed values into a new [`UnthBuf`], using the smallest bit-size that fits the largest value.
+    /// 
+    /// # Errors
+    /// - If no values were pushed, as the buffer would be of 0 capacity.
+    pub fn finish<CL: CellLayout>(self) -> Result<UnthBuf<CL>, UnthBufError> {
+        if self.values.is_empty() {return Err(UnthBufError::ZeroCapacity)}
+        
+        let bits = UnthBuf::<CL>::bits_for_value(self.max);
+        Ok(UnthBuf::new_from_sized_iter(bits, self.values.into_iter()))
+    }
+}
+
+impl<CL: CellLayout> FromIterator<usize> for UnthBuf<CL> {
+    /// Collects all elements into a new [`UnthBuf`], using the smallest bit-size that fits the largest element.
+    /// 
+    /// The elements are buffered first, as the bit-size is only known once all of them were seen.
+    /// 
+    /// # Panic
+    /// - If the iterator is empty, as the buffer would be of 0 capacity.
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut writer = UnthBufWriter::new();
+        iter.into_iter().for_each(|value| writer.push(value));
+        writer.finish().expect("cannot collect an empty iterator into a buffer of 0 capacity")
+    }
+}